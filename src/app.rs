@@ -0,0 +1,2188 @@
+mod plot;
+mod settings;
+mod task;
+
+use plot::{category, gradient, Chart, Heatmap, PlotExports, Series};
+use settings::DEFAULT_VARIABLE_SET;
+use task::Task;
+use atmos::chamber::{Chamber, Design, Feed};
+use atmos::constants::expressions;
+use atmos::diff::{compare, report, Change, Difference, Snapshot};
+use atmos::eval::sweep_values;
+use atmos::export::{Format, Table};
+use atmos::history::{history, History};
+use atmos::mixture::{Mixture, Physics, Properties};
+use atmos::phase::{phase_map_until, Axis, Parameter, PhaseMap};
+use atmos::reaction::{requirement_warnings, Effect};
+use atmos::simulation::{Run, Simulation, Step};
+use atmos::source::{CONSTANTS_PATH, DEFAULT_OWNER, DEFAULT_REF, DEFAULT_REPO};
+use atmos::teg::Generator;
+use atmos::variables::{read_variables, write_variables};
+use atmos::{fetch_and_parse_constants, fetch_and_parse_gases, fetch_and_parse_reactions, fetch_gas_names, fetch_generator, Constant, DataSource, DependencyGraph, Error, Evaluator, Gas, Origin, Reaction};
+use copypasta::{ClipboardContext, ClipboardProvider};
+use egui::{CentralPanel, Context, Frame, Stroke, TextEdit, TopBottomPanel, Ui};
+use egui_extras::{Column, TableBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+pub use settings::Settings;
+
+type Loaded<T> = atmos::Result<(T, Origin)>;
+type Reactions = (Vec<Reaction>, Vec<String>);
+type Cells = Vec<(egui::Color32, String)>;
+
+// The fire constants the chamber estimate depends on, listed so changes between sources are easy to spot.
+const CHAMBER_CONSTANTS: &[&str] = &[
+    "FirePlasmaEnergyReleased",
+    "FireHydrogenEnergyReleased",
+    "MinimumTritiumOxyburnEnergy",
+    "PlasmaMinimumBurnTemperature",
+    "PlasmaUpperTemperature",
+    "OxygenBurnRateBase",
+    "PlasmaOxygenFullburn",
+    "PlasmaBurnRateDelta",
+    "SuperSaturationThreshold",
+    "SuperSaturationEnds",
+    "TritiumBurnOxyFactor",
+    "TritiumBurnTritFactor",
+];
+
+#[derive(Default)]
+pub struct MyApp {
+    constants: HashMap<String, String>,
+    constant_list: Vec<Constant>,
+    evaluated_constants: HashMap<String, Result<String, String>>,
+    dependency_graph: DependencyGraph,
+    user_variables: HashMap<String, String>,
+    sorted_constants: Vec<Constant>,
+    filtered_constants: Vec<Constant>,
+    selected_constant: Option<String>,
+    gases: Vec<Gas>,
+    reactions: Vec<Reaction>,
+    gas_names: Vec<String>,
+    selected_tab: Tab,
+    sort_order: SortOrder,
+    calculator_input: String,
+    calculator_output: String,
+    search_query: String,
+    new_variable_name: String,
+    new_variable_value: String,
+    gases_loaded: bool,
+    reactions_loaded: bool,
+    source: DataSource,
+    source_kind: SourceKind,
+    source_owner_input: String,
+    source_repo_input: String,
+    source_ref_input: String,
+    source_url_input: String,
+    local_path_input: String,
+    source_error: Option<Error>,
+    constants_origin: Option<Origin>,
+    gases_origin: Option<Origin>,
+    reactions_origin: Option<Origin>,
+    constants_task: Option<Task<Loaded<Vec<Constant>>>>,
+    gases_task: Option<Task<Loaded<Vec<Gas>>>>,
+    reactions_task: Option<Task<Loaded<Reactions>>>,
+    constants_error: Option<Error>,
+    gases_error: Option<Error>,
+    reactions_error: Option<Error>,
+    compare_old_input: String,
+    compare_new_input: String,
+    compare_task: Option<Task<atmos::Result<(Snapshot, Snapshot)>>>,
+    compare_error: Option<Error>,
+    compare_origins: Option<(Origin, Origin)>,
+    differences: Vec<Difference>,
+    report_path_input: String,
+    report_status: Option<String>,
+    export_format: Format,
+    export_path_input: String,
+    export_status: Option<String>,
+    variable_set: String,
+    variable_sets: BTreeMap<String, HashMap<String, String>>,
+    new_variable_set_input: String,
+    variables_path_input: String,
+    variables_status: Option<String>,
+    saved_settings: Settings,
+    last_save: Option<Instant>,
+    history_path_input: String,
+    history_task: Option<Task<atmos::Result<History>>>,
+    history_error: Option<Error>,
+    history: Option<History>,
+    history_query: String,
+    selected_timeline: Option<usize>,
+    mixture: Option<Mixture>,
+    mixture_by_pressure: bool,
+    mixing_giver: Option<Mixture>,
+    mixing_receiver: Option<Mixture>,
+    mixing_combined_volume: bool,
+    simulation_mixture: Option<Mixture>,
+    simulation_ticks: usize,
+    simulation_tile: bool,
+    simulation_run: Option<Run>,
+    phase_mixture: Option<Mixture>,
+    phase_axes: Option<(Axis, Axis)>,
+    phase_ticks: usize,
+    phase_metric: PhaseMetric,
+    phase_task: Option<Task<Option<Phase>>>,
+    phase: Option<Phase>,
+    chamber: Option<Chamber>,
+    chamber_requested: Option<Chamber>,
+    chamber_task: Option<Task<Result<Design, String>>>,
+    chamber_design: Option<Result<Design, String>>,
+    teg_hot: Option<Mixture>,
+    teg_cold: Option<Mixture>,
+    teg_interval: f64,
+    teg_generator: Option<Generator>,
+    teg_loaded: bool,
+    teg_origin: Option<Origin>,
+    teg_task: Option<Task<Loaded<Option<Generator>>>>,
+    teg_error: Option<Error>,
+    plot_exports: PlotExports,
+    sweep_variable: String,
+    sweep_from: f64,
+    sweep_to: f64,
+    sweep_steps: usize,
+    sweep: Option<Sweep>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+enum Tab {
+    #[default]
+    ParseConstants,
+    Dependencies,
+    Calculator,
+    Gases,
+    Reactions,
+    Mixture,
+    Mixing,
+    Simulation,
+    PhaseMap,
+    Chamber,
+    Teg,
+    Compare,
+    History,
+}
+
+#[derive(PartialEq, Default)]
+enum SourceKind {
+    #[default]
+    GitHub,
+    Url,
+    Local,
+}
+
+struct Sweep {
+    expression: String,
+    variable: String,
+    values: Vec<f64>,
+    results: Vec<Result<f64, String>>,
+}
+
+struct Phase {
+    map: PhaseMap,
+    x_label: String,
+    y_label: String,
+}
+
+#[derive(Clone, Copy, PartialEq, Default)]
+enum PhaseMetric {
+    #[default]
+    EnergyReleased,
+    TemperatureChange,
+    FinalPressure,
+    Reactions,
+}
+
+enum ExportTarget {
+    Clipboard,
+    File,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+enum SortOrder {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+impl MyApp {
+    pub fn new(ctx: &Context, settings: Settings) -> Self {
+        let mut app = Self {
+            selected_tab: settings.selected_tab,
+            sort_order: settings.sort_order,
+            search_query: settings.search_query.clone(),
+            export_format: settings.export_format.as_deref().and_then(Format::parse).unwrap_or_default(),
+            variable_set: if settings.variable_set.is_empty() { DEFAULT_VARIABLE_SET.to_string() } else { settings.variable_set.clone() },
+            variable_sets: settings.variable_sets.clone(),
+            simulation_ticks: 30,
+            simulation_tile: true,
+            phase_ticks: 10,
+            teg_interval: 1.0,
+            sweep_to: 100.0,
+            sweep_steps: 50,
+            ..Self::default()
+        };
+        app.user_variables = app.variable_sets.get(&app.variable_set).cloned().unwrap_or_default();
+        if let Some(source) = settings.source.as_deref().and_then(|spec| DataSource::parse(spec).ok()) {
+            app.source = source;
+        }
+        app.restore_source_inputs();
+        app.saved_settings = settings;
+        match app.selected_tab {
+            Tab::Gases => app.fetch_and_parse_gases(ctx),
+            Tab::Reactions => app.fetch_and_parse_reactions(ctx),
+            Tab::Mixture | Tab::Mixing => app.load_mixture_data(ctx),
+            Tab::Simulation | Tab::PhaseMap | Tab::Chamber => app.load_simulation_data(ctx),
+            Tab::Teg => app.load_teg_data(ctx),
+            _ => {}
+        }
+        app
+    }
+
+    fn settings(&self, ctx: &Context) -> Settings {
+        let mut variable_sets = self.variable_sets.clone();
+        variable_sets.insert(self.variable_set.clone(), self.user_variables.clone());
+        Settings {
+            source: (self.source != DataSource::default()).then(|| self.source.to_string()),
+            selected_tab: self.selected_tab,
+            sort_order: self.sort_order,
+            search_query: self.search_query.clone(),
+            export_format: Some(self.export_format.to_string()),
+            variable_set: self.variable_set.clone(),
+            variable_sets,
+            window_size: ctx.input(|input| input.viewport().inner_rect).map(|rect| [rect.width(), rect.height()]),
+        }
+    }
+
+    fn persist_settings(&mut self, ctx: &Context) {
+        let settings = self.settings(ctx);
+        if settings == self.saved_settings {
+            return;
+        }
+        // Resizing the window changes the settings every frame, so writes are spaced out.
+        let delay = Duration::from_secs(1);
+        match self.last_save.map(|last_save| last_save.elapsed()) {
+            Some(elapsed) if elapsed < delay => ctx.request_repaint_after(delay - elapsed),
+            _ => {
+                settings.save();
+                self.saved_settings = settings;
+                self.last_save = Some(Instant::now());
+            }
+        }
+    }
+
+    fn restore_source_inputs(&mut self) {
+        match &self.source {
+            DataSource::GitHub { owner, repo, git_ref } => {
+                self.source_kind = SourceKind::GitHub;
+                if self.source != DataSource::default() {
+                    self.source_owner_input = owner.clone();
+                    self.source_repo_input = repo.clone();
+                    self.source_ref_input = git_ref.clone();
+                }
+            }
+            DataSource::Url(url) => {
+                self.source_kind = SourceKind::Url;
+                self.source_url_input = url.clone();
+            }
+            DataSource::Local(root) => {
+                self.source_kind = SourceKind::Local;
+                self.local_path_input = root.display().to_string();
+            }
+        }
+    }
+
+    fn select_variable_set(&mut self, name: String) {
+        let variables = self.variable_sets.remove(&name).unwrap_or_default();
+        let previous = std::mem::replace(&mut self.user_variables, variables);
+        self.variable_sets.insert(std::mem::replace(&mut self.variable_set, name), previous);
+        self.evaluate_constants();
+    }
+
+    fn delete_variable_set(&mut self) {
+        self.variable_sets.remove(&self.variable_set);
+        let next = self.variable_sets.keys().next().cloned().unwrap_or_else(|| DEFAULT_VARIABLE_SET.to_string());
+        self.user_variables = self.variable_sets.remove(&next).unwrap_or_default();
+        self.variable_set = next;
+        self.evaluate_constants();
+    }
+
+    fn import_variables(&mut self) {
+        let path = self.variables_path_input.trim().to_string();
+        self.variables_status = Some(match read_variables(Path::new(&path)) {
+            Ok(variables) => {
+                let count = variables.len();
+                self.user_variables.extend(variables);
+                self.evaluate_constants();
+                format!("Imported {} variables from {}", count, path)
+            }
+            Err(err) => err.to_string(),
+        });
+    }
+
+    fn export_variables(&mut self) {
+        let path = match self.variables_path_input.trim() {
+            "" => format!("{}.yml", self.variable_set),
+            path => path.to_string(),
+        };
+        self.variables_status = Some(match write_variables(Path::new(&path), &self.user_variables) {
+            Ok(()) => format!("Saved {} variables to {}", self.user_variables.len(), path),
+            Err(err) => err.to_string(),
+        });
+    }
+
+    fn fetch_and_parse_constants(&mut self, ctx: &Context) {
+        let source = self.source.clone();
+        self.constants_error = None;
+        self.constants_task = Some(Task::spawn(ctx, move || fetch_and_parse_constants(&source)));
+    }
+
+    fn fetch_and_parse_gases(&mut self, ctx: &Context) {
+        let source = self.source.clone();
+        self.gases_error = None;
+        self.gases_task = Some(Task::spawn(ctx, move || fetch_and_parse_gases(&source)));
+    }
+
+    fn fetch_and_parse_reactions(&mut self, ctx: &Context) {
+        let source = self.source.clone();
+        self.reactions_error = None;
+        self.reactions_task = Some(Task::spawn(ctx, move || {
+            let (reactions, origin) = fetch_and_parse_reactions(&source)?;
+            Ok(((reactions, fetch_gas_names(&source)?), origin))
+        }));
+    }
+
+    fn load_mixture_data(&mut self, ctx: &Context) {
+        if self.constant_list.is_empty() && self.constants_task.is_none() {
+            self.fetch_and_parse_constants(ctx);
+        }
+        if !self.gases_loaded && self.gases_task.is_none() {
+            self.fetch_and_parse_gases(ctx);
+        }
+    }
+
+    fn load_simulation_data(&mut self, ctx: &Context) {
+        self.load_mixture_data(ctx);
+        if !self.reactions_loaded && self.reactions_task.is_none() {
+            self.fetch_and_parse_reactions(ctx);
+        }
+    }
+
+    fn fetch_generator(&mut self, ctx: &Context) {
+        let source = self.source.clone();
+        self.teg_error = None;
+        self.teg_task = Some(Task::spawn(ctx, move || fetch_generator(&source)));
+    }
+
+    fn load_teg_data(&mut self, ctx: &Context) {
+        self.load_mixture_data(ctx);
+        if !self.teg_loaded && self.teg_task.is_none() {
+            self.fetch_generator(ctx);
+        }
+    }
+
+    fn mixture_physics(&mut self, ui: &mut Ui, ctx: &Context) -> Option<Physics> {
+        progress(ui, &mut self.constants_task, "constants");
+        progress(ui, &mut self.gases_task, "gases");
+        if error_panel(ui, &self.constants_error, "constants") {
+            self.fetch_and_parse_constants(ctx);
+        }
+        if error_panel(ui, &self.gases_error, "gases") {
+            self.fetch_and_parse_gases(ctx);
+        }
+        if self.constant_list.is_empty() || !self.gases_loaded {
+            if self.constants_task.is_none() && self.gases_task.is_none() && ui.button("Load Constants and Gases").clicked() {
+                self.load_mixture_data(ctx);
+            }
+            return None;
+        }
+        match Physics::from_constants(&self.evaluated_constants) {
+            Ok(physics) => Some(physics),
+            Err(err) => {
+                ui.colored_label(ui.visuals().error_fg_color, err);
+                None
+            }
+        }
+    }
+
+    fn compare_sources(&mut self, ctx: &Context) {
+        let old = DataSource::parse(&self.compare_old_input);
+        let new = if self.compare_new_input.trim().is_empty() { Ok(self.source.clone()) } else { DataSource::parse(&self.compare_new_input) };
+        match (old, new) {
+            (Ok(old), Ok(new)) => {
+                self.compare_error = None;
+                self.compare_task = Some(Task::spawn(ctx, move || Ok((Snapshot::load(&old)?, Snapshot::load(&new)?))));
+            }
+            (Err(err), _) | (_, Err(err)) => self.compare_error = Some(err),
+        }
+    }
+
+    fn report(&self) -> String {
+        match &self.compare_origins {
+            Some((old, new)) => report(&self.differences, old, new),
+            None => String::new(),
+        }
+    }
+
+    fn save_report(&mut self) {
+        let path = self.report_path_input.trim();
+        self.report_status = Some(match std::fs::write(path, self.report()) {
+            Ok(()) => format!("Saved to {}", path),
+            Err(err) => format!("Error writing {}: {}", path, err),
+        });
+    }
+
+    fn load_history(&mut self, ctx: &Context) {
+        let path = self.history_path_input.trim();
+        let source = if path.is_empty() { Ok(self.source.clone()) } else { DataSource::local(path) };
+        match source {
+            Ok(DataSource::Local(root)) => {
+                self.history_error = None;
+                self.history_task = Some(Task::spawn(ctx, move || history(&root, None)));
+            }
+            Ok(_) => self.history_error = Some(Error::InvalidSource("History needs a local checkout with git history".to_string())),
+            Err(err) => self.history_error = Some(err),
+        }
+    }
+
+    fn poll_tasks(&mut self) {
+        match Task::poll(&mut self.constants_task) {
+            Some(Ok((constants, origin))) => {
+                self.constants = expressions(&constants);
+                self.dependency_graph = DependencyGraph::new(&constants);
+                self.constant_list = constants;
+                self.constants_origin = Some(origin);
+                self.evaluate_constants();
+                self.sort_and_filter_constants();
+            }
+            Some(Err(err)) => self.constants_error = Some(err),
+            None => {}
+        }
+        match Task::poll(&mut self.gases_task) {
+            Some(Ok((gases, origin))) => {
+                self.gases = gases;
+                self.gases_origin = Some(origin);
+                self.gases_loaded = true;
+            }
+            Some(Err(err)) => self.gases_error = Some(err),
+            None => {}
+        }
+        match Task::poll(&mut self.history_task) {
+            Some(Ok(history)) => {
+                self.history = Some(history);
+                self.selected_timeline = None;
+            }
+            Some(Err(err)) => self.history_error = Some(err),
+            None => {}
+        }
+        match Task::poll(&mut self.compare_task) {
+            Some(Ok((old, new))) => {
+                self.differences = compare(&old, &new);
+                self.compare_origins = Some((old.origin, new.origin));
+                self.report_status = None;
+            }
+            Some(Err(err)) => self.compare_error = Some(err),
+            None => {}
+        }
+        match Task::poll(&mut self.reactions_task) {
+            Some(Ok(((reactions, gas_names), origin))) => {
+                self.reactions = reactions;
+                self.gas_names = gas_names;
+                self.reactions_origin = Some(origin);
+                self.reactions_loaded = true;
+            }
+            Some(Err(err)) => self.reactions_error = Some(err),
+            None => {}
+        }
+        if let Some(phase) = Task::poll(&mut self.phase_task).flatten() {
+            self.phase = Some(phase);
+        }
+        if let Some(design) = Task::poll(&mut self.chamber_task) {
+            self.chamber_design = Some(design);
+        }
+        match Task::poll(&mut self.teg_task) {
+            Some(Ok((generator, origin))) => {
+                self.teg_generator = generator;
+                self.teg_origin = Some(origin);
+                self.teg_loaded = true;
+            }
+            Some(Err(err)) => self.teg_error = Some(err),
+            None => {}
+        }
+    }
+
+    fn apply_source_inputs(&mut self, ctx: &Context) {
+        let or_default = |input: &str, default: &str| {
+            let input = input.trim();
+            if input.is_empty() { default.to_string() } else { input.to_string() }
+        };
+        let source = match self.source_kind {
+            SourceKind::GitHub => Ok(DataSource::github(
+                &or_default(&self.source_owner_input, DEFAULT_OWNER),
+                &or_default(&self.source_repo_input, DEFAULT_REPO),
+                &or_default(&self.source_ref_input, DEFAULT_REF),
+            )),
+            SourceKind::Url => DataSource::url(&self.source_url_input),
+            SourceKind::Local => DataSource::local(self.local_path_input.trim()),
+        };
+        match source {
+            Ok(source) => {
+                self.source_error = None;
+                self.set_source(source, ctx);
+            }
+            Err(err) => self.source_error = Some(err),
+        }
+    }
+
+    fn set_source(&mut self, source: DataSource, ctx: &Context) {
+        if self.source == source {
+            return;
+        }
+        self.source = source;
+        self.constants.clear();
+        self.constant_list.clear();
+        self.evaluated_constants.clear();
+        self.dependency_graph = DependencyGraph::default();
+        self.constants_origin = None;
+        self.gases_origin = None;
+        self.reactions_origin = None;
+        self.sort_and_filter_constants();
+        self.gases.clear();
+        self.reactions.clear();
+        self.gas_names.clear();
+        self.mixture = None;
+        self.mixing_giver = None;
+        self.mixing_receiver = None;
+        self.simulation_mixture = None;
+        self.simulation_run = None;
+        self.phase_mixture = None;
+        self.phase_axes = None;
+        self.phase_task = None;
+        self.phase = None;
+        self.chamber = None;
+        self.chamber_requested = None;
+        self.chamber_task = None;
+        self.chamber_design = None;
+        self.teg_hot = None;
+        self.teg_cold = None;
+        self.teg_generator = None;
+        self.teg_origin = None;
+        self.gases_loaded = false;
+        self.reactions_loaded = false;
+        self.teg_loaded = false;
+        self.constants_task = None;
+        self.gases_task = None;
+        self.reactions_task = None;
+        self.teg_task = None;
+        self.constants_error = None;
+        self.gases_error = None;
+        self.reactions_error = None;
+        self.teg_error = None;
+        match self.selected_tab {
+            Tab::Gases => self.fetch_and_parse_gases(ctx),
+            Tab::Reactions => self.fetch_and_parse_reactions(ctx),
+            Tab::Mixture | Tab::Mixing => self.load_mixture_data(ctx),
+            Tab::Simulation | Tab::PhaseMap | Tab::Chamber => self.load_simulation_data(ctx),
+            Tab::Teg => self.load_teg_data(ctx),
+            _ => {}
+        }
+    }
+
+    fn sort_and_filter_constants(&mut self) {
+        let mut sorted = self.constant_list.clone();
+        sorted.sort_by(|a, b| match self.sort_order {
+            SortOrder::Ascending => a.name.cmp(&b.name),
+            SortOrder::Descending => b.name.cmp(&a.name),
+        });
+        self.sorted_constants = sorted;
+        self.filter_constants();
+    }
+
+    fn filter_constants(&mut self) {
+        if self.search_query.is_empty() {
+            self.filtered_constants = self.sorted_constants.clone();
+        } else {
+            let query = self.search_query.to_lowercase();
+            self.filtered_constants = self.sorted_constants.iter()
+                .filter(|constant| constant.name.to_lowercase().contains(&query) || constant.initializer.to_lowercase().contains(&query))
+                .cloned()
+                .collect();
+        }
+    }
+
+    fn export(&mut self, table: Table, name: &str, target: ExportTarget) {
+        let text = table.render(self.export_format);
+        self.export_status = Some(match target {
+            ExportTarget::Clipboard => match copy_to_clipboard(text) {
+                Ok(()) => format!("Copied {} rows as {}", table.rows.len(), self.export_format),
+                Err(err) => err,
+            },
+            ExportTarget::File => {
+                let path = match self.export_path_input.trim() {
+                    "" => format!("{}.{}", name, self.export_format.extension()),
+                    path => path.to_string(),
+                };
+                match std::fs::write(&path, text) {
+                    Ok(()) => format!("Saved {} rows to {}", table.rows.len(), path),
+                    Err(err) => format!("Error writing {}: {}", path, err),
+                }
+            }
+        });
+    }
+
+    fn evaluator(&self) -> Evaluator<'_> {
+        Evaluator::new(&self.constants, &self.user_variables)
+    }
+
+    fn evaluate_constants(&mut self) {
+        self.evaluated_constants = self.evaluator().evaluate_constants();
+        self.chamber_requested = None;
+        self.chamber_task = None;
+        self.chamber_design = None;
+    }
+
+    fn delete_user_variable(&mut self, name: &str) {
+        self.user_variables.remove(name);
+        self.evaluate_constants();
+    }
+}
+
+fn progress<T: Send + 'static>(ui: &mut Ui, task: &mut Option<Task<T>>, what: &str) {
+    let Some(elapsed) = task.as_ref().map(Task::elapsed) else {
+        return;
+    };
+    ui.horizontal(|ui| {
+        ui.spinner();
+        ui.label(format!("Loading {}... {}s", what, elapsed.as_secs()));
+        if ui.button("Cancel").clicked() {
+            *task = None;
+        }
+    });
+}
+
+fn error_panel(ui: &mut Ui, error: &Option<Error>, what: &str) -> bool {
+    let Some(error) = error else {
+        return false;
+    };
+    let color = ui.visuals().error_fg_color;
+    Frame::group(ui.style())
+        .stroke(Stroke::new(1.0, color))
+        .show(ui, |ui| {
+            ui.colored_label(color, format!("Failed to load {}", what));
+            ui.label(error.to_string());
+            ui.button("Retry").clicked()
+        })
+        .inner
+}
+
+fn constant_details(ui: &mut Ui, constant: &Constant, origin: &Option<Origin>) -> bool {
+    let mut close = false;
+    ui.group(|ui| {
+        ui.horizontal(|ui| {
+            ui.strong(format!("{}.{}", constant.class, constant.name));
+            ui.label(format!("{} = {}", constant.ty, constant.initializer));
+            if ui.small_button("Close").clicked() {
+                close = true;
+            }
+        });
+        ui.label(constant.description.as_deref().unwrap_or("No description."));
+        let location = origin.as_ref().and_then(|origin| origin.source.line_url(CONSTANTS_PATH, constant.line));
+        match location {
+            Some(url) if url.starts_with("http") => {
+                ui.hyperlink_to(format!("{}:{}", CONSTANTS_PATH, constant.line), url);
+            }
+            Some(path) => {
+                ui.label(path);
+            }
+            None => {
+                ui.label(format!("{}:{}", CONSTANTS_PATH, constant.line));
+            }
+        }
+    });
+    close
+}
+
+fn gas_details(ui: &mut Ui, gas: &Gas) {
+    let title = match &gas.id {
+        Some(id) => format!("{} ({})", gas.name, id),
+        None => gas.name.clone(),
+    };
+    egui::CollapsingHeader::new(title).id_source(("gas", &gas.name)).show(ui, |ui| {
+        egui::Grid::new(("gas_fields", &gas.name)).striped(true).show(ui, |ui| {
+            for (field, value) in gas.fields() {
+                ui.label(field);
+                match value {
+                    Some(value) if field == "color" => {
+                        ui.horizontal(|ui| {
+                            if let Some(color) = gas_color(gas) {
+                                ui.colored_label(color, "■");
+                            }
+                            ui.label(value);
+                        });
+                    }
+                    Some(value) => {
+                        ui.label(value);
+                    }
+                    None => {
+                        ui.weak("n/a");
+                    }
+                }
+                ui.end_row();
+            }
+            for (field, value) in gas.other_fields() {
+                ui.label(field).on_hover_text("Not a known gas field");
+                ui.label(value);
+                ui.end_row();
+            }
+        });
+    });
+}
+
+fn amount_mode(ui: &mut Ui, by_pressure: &mut bool) {
+    ui.horizontal(|ui| {
+        ui.label("Amounts:");
+        ui.selectable_value(by_pressure, false, "Moles");
+        ui.selectable_value(by_pressure, true, "Partial pressures");
+    });
+}
+
+fn mixture_editor(ui: &mut Ui, id: &str, mixture: &mut Mixture, gases: &[Gas], physics: &Physics, by_pressure: bool) {
+    mixture.moles.resize(gases.len(), 0.0);
+    ui.horizontal(|ui| {
+        ui.label("Temperature:");
+        ui.add(egui::DragValue::new(&mut mixture.temperature).range(0.0..=f64::MAX).speed(1.0).suffix(" K"));
+        ui.label("Volume:");
+        ui.add(egui::DragValue::new(&mut mixture.volume).range(0.0..=f64::MAX).speed(10.0).suffix(" L"));
+        if ui.button("Clear").clicked() {
+            mixture.moles.iter_mut().for_each(|moles| *moles = 0.0);
+        }
+    });
+    egui::ScrollArea::vertical().id_source((id, "gases")).show(ui, |ui| {
+        egui::Grid::new((id, "amounts")).striped(true).show(ui, |ui| {
+            for (gas, moles) in gases.iter().zip(mixture.moles.iter_mut()) {
+                ui.label(&gas.name);
+                if by_pressure {
+                    let mut pressure = *moles * physics.r * mixture.temperature / mixture.volume.max(f64::MIN_POSITIVE);
+                    if ui.add(egui::DragValue::new(&mut pressure).range(0.0..=f64::MAX).speed(1.0).suffix(" kPa")).changed() {
+                        *moles = physics.moles_for_pressure(pressure, mixture.temperature, mixture.volume);
+                    }
+                } else {
+                    ui.add(egui::DragValue::new(moles).range(0.0..=f64::MAX).speed(0.1).suffix(" mol"));
+                }
+                ui.end_row();
+            }
+        });
+    });
+}
+
+fn mixture_properties(ui: &mut Ui, id: &str, properties: &Properties) {
+    let optional = |value: Option<f64>| value.map_or("n/a".to_string(), |value| format!("{:.4}", value));
+    egui::Grid::new((id, "properties")).striped(true).show(ui, |ui| {
+        for (label, value) in [
+            ("Temperature", format!("{:.3} K", properties.temperature)),
+            ("Volume", format!("{} L", properties.volume)),
+            ("Total moles", format!("{:.4} mol", properties.total_moles)),
+            ("Pressure", format!("{:.3} kPa", properties.pressure)),
+            ("Heat capacity", format!("{:.3} J/K", properties.heat_capacity)),
+            ("Thermal energy", format!("{:.3} J", properties.thermal_energy)),
+            ("Mass", format!("{:.3} g", properties.mass)),
+            ("Molar mass", format!("{} g/mol", optional(properties.molar_mass))),
+            ("Heat capacity ratio", optional(properties.heat_capacity_ratio)),
+        ] {
+            ui.label(label);
+            ui.label(value);
+            ui.end_row();
+        }
+    });
+    if properties.components.is_empty() {
+        return;
+    }
+    ui.separator();
+    egui::Grid::new((id, "components")).striped(true).show(ui, |ui| {
+        for heading in ["Gas", "Fraction", "Partial Pressure", "Heat Capacity", "Mass"] {
+            ui.strong(heading);
+        }
+        ui.end_row();
+        for component in &properties.components {
+            ui.label(&component.gas);
+            ui.label(format!("{:.2}%", component.fraction * 100.0));
+            ui.label(format!("{:.3} kPa", component.partial_pressure));
+            ui.label(format!("{:.3} J/K", component.heat_capacity));
+            ui.label(format!("{:.3} g", component.mass));
+            ui.end_row();
+        }
+    });
+}
+
+fn dependency_problems(ui: &mut Ui, graph: &DependencyGraph, selected: &mut Option<String>) {
+    let cycles = graph.cycles();
+    let dangling = graph.dangling();
+    if cycles.is_empty() && dangling.is_empty() {
+        ui.weak("No circular or dangling references.");
+        return;
+    }
+    let color = ui.visuals().warn_fg_color;
+    ui.collapsing(format!("{} circular, {} dangling references", cycles.len(), dangling.len()), |ui| {
+        for cycle in cycles {
+            ui.horizontal_wrapped(|ui| {
+                ui.colored_label(color, "cycle:");
+                for name in cycle {
+                    if ui.link(&name).clicked() {
+                        *selected = Some(name);
+                    }
+                }
+            });
+        }
+        for reference in dangling {
+            ui.horizontal(|ui| {
+                ui.colored_label(color, "dangling:");
+                if ui.link(&reference.constant).clicked() {
+                    *selected = Some(reference.constant.clone());
+                }
+                ui.label(format!("references unknown {}", reference.reference));
+            });
+        }
+    });
+}
+
+fn dependency_tree(ui: &mut Ui, graph: &DependencyGraph, path: &mut Vec<String>, dependents: bool, selected: &mut Option<String>) {
+    let name = path[path.len() - 1].clone();
+    let edges = if dependents { graph.dependents(&name) } else { graph.dependencies(&name) };
+    for next in edges {
+        if path.contains(next) {
+            ui.horizontal(|ui| {
+                ui.label(next);
+                ui.colored_label(ui.visuals().warn_fg_color, "(cycle)");
+            });
+        } else if !graph.contains(next) {
+            ui.horizontal(|ui| {
+                ui.label(next);
+                ui.colored_label(ui.visuals().error_fg_color, "(not found)");
+            });
+        } else {
+            let has_children = !if dependents { graph.dependents(next) } else { graph.dependencies(next) }.is_empty();
+            path.push(next.clone());
+            if has_children {
+                let id = ui.make_persistent_id((dependents, path.as_slice()));
+                egui::collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), id, false)
+                    .show_header(ui, |ui| {
+                        if ui.link(next).clicked() {
+                            *selected = Some(next.clone());
+                        }
+                    })
+                    .body(|ui| dependency_tree(ui, graph, path, dependents, selected));
+            } else if ui.link(next).clicked() {
+                *selected = Some(next.clone());
+            }
+            path.pop();
+        }
+    }
+}
+
+fn simulation_results(ui: &mut Ui, run: &Run, gases: &[Gas], exports: &mut PlotExports) {
+    let color = ui.visuals().warn_fg_color;
+    for warning in &run.warnings {
+        ui.colored_label(color, warning);
+    }
+    let column = |value: &dyn Fn(&Step) -> f64| run.steps.iter().map(|step| [step.tick as f64, value(step)]).collect::<Vec<_>>();
+    let composition: Vec<Series> = gases
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| run.steps.iter().any(|step| step.moles[*index] > 0.0))
+        .map(|(index, gas)| Series::new(gas.name.clone(), column(&|step| step.moles[index])).color(gas_color(gas)))
+        .collect();
+    egui::ScrollArea::vertical().id_source("simulation_results").show(ui, |ui| {
+        Chart::new("temperature", "Tick", "Temperature (K)", vec![Series::new("Temperature", column(&|step| step.temperature))]).show(ui, exports);
+        Chart::new("pressure", "Tick", "Pressure (kPa)", vec![Series::new("Pressure", column(&|step| step.pressure))]).show(ui, exports);
+        Chart::new("composition", "Tick", "Moles", composition).show(ui, exports);
+        ui.collapsing("Reactions per tick", |ui| {
+            for step in run.steps.iter().filter(|step| !step.reactions.is_empty()) {
+                ui.label(format!("{}: {}", step.tick, step.reactions.join(", ")));
+            }
+        });
+    });
+}
+
+fn default_phase_axes(gases: &[Gas]) -> (Axis, Axis) {
+    let temperature = Axis {
+        parameter: Parameter::Temperature,
+        from: 300.0,
+        to: 2000.0,
+        steps: 30,
+    };
+    let find = |name: &str| gases.iter().position(|gas| gas.matches(name));
+    let y = match (find("oxygen"), find("plasma")) {
+        (Some(gas), Some(other)) => Axis {
+            parameter: Parameter::Ratio { gas, other },
+            from: 0.5,
+            to: 20.0,
+            steps: 30,
+        },
+        _ => Axis {
+            parameter: Parameter::Pressure,
+            from: 100.0,
+            to: 5000.0,
+            steps: 30,
+        },
+    };
+    (temperature, y)
+}
+
+fn phase_axis_editor(ui: &mut Ui, id: &str, axis: &mut Axis, gases: &[Gas]) {
+    let before = axis.parameter;
+    let ratio = match axis.parameter {
+        Parameter::Ratio { gas, other } => Parameter::Ratio { gas, other },
+        _ => Parameter::Ratio { gas: 0, other: gases.len().min(2).saturating_sub(1) },
+    };
+    egui::ComboBox::from_id_source((id, "parameter"))
+        .selected_text(match axis.parameter {
+            Parameter::Temperature => "Temperature",
+            Parameter::Pressure => "Pressure",
+            Parameter::Ratio { .. } => "Ratio",
+        })
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut axis.parameter, Parameter::Temperature, "Temperature");
+            ui.selectable_value(&mut axis.parameter, Parameter::Pressure, "Pressure");
+            if !gases.is_empty() {
+                ui.selectable_value(&mut axis.parameter, ratio, "Ratio");
+            }
+        });
+    if std::mem::discriminant(&axis.parameter) != std::mem::discriminant(&before) {
+        (axis.from, axis.to) = match axis.parameter {
+            Parameter::Temperature => (300.0, 2000.0),
+            Parameter::Pressure => (100.0, 5000.0),
+            Parameter::Ratio { .. } => (0.5, 20.0),
+        };
+    }
+    let suffix = match &mut axis.parameter {
+        Parameter::Temperature => " K",
+        Parameter::Pressure => " kPa",
+        Parameter::Ratio { gas, other } => {
+            for (salt, index) in [("gas", gas), ("other", other)] {
+                egui::ComboBox::from_id_source((id, salt)).selected_text(&gases[*index].name).show_ui(ui, |ui| {
+                    for (i, gas) in gases.iter().enumerate() {
+                        ui.selectable_value(index, i, &gas.name);
+                    }
+                });
+            }
+            ""
+        }
+    };
+    ui.label("from");
+    ui.add(egui::DragValue::new(&mut axis.from).range(0.0..=f64::MAX).speed(1.0).suffix(suffix));
+    ui.label("to");
+    ui.add(egui::DragValue::new(&mut axis.to).range(0.0..=f64::MAX).speed(1.0).suffix(suffix));
+    ui.label("steps");
+    ui.add(egui::DragValue::new(&mut axis.steps).range(1..=200));
+}
+
+fn phase_cells(map: &PhaseMap, metric: PhaseMetric) -> (&'static str, Cells, Cells) {
+    let idle = egui::Color32::from_gray(60);
+    if metric == PhaseMetric::Reactions {
+        let mut labels: Vec<String> = Vec::new();
+        let cells = map
+            .outcomes
+            .iter()
+            .map(|outcome| {
+                let label = if outcome.reactions.is_empty() { "none".to_string() } else { outcome.reactions.join(", ") };
+                if outcome.reactions.is_empty() {
+                    return (idle, label);
+                }
+                let index = labels.iter().position(|known| *known == label).unwrap_or_else(|| {
+                    labels.push(label.clone());
+                    labels.len() - 1
+                });
+                (category(index), label)
+            })
+            .collect();
+        let mut legend: Cells = labels.into_iter().enumerate().map(|(index, label)| (category(index), label)).collect();
+        legend.push((idle, "none".to_string()));
+        return ("Reactions", cells, legend);
+    }
+
+    let (label, unit, value): (&'static str, &str, fn(&atmos::phase::Outcome) -> f64) = match metric {
+        PhaseMetric::EnergyReleased => ("Energy released (J)", "J", |outcome| outcome.energy_released),
+        PhaseMetric::TemperatureChange => ("Temperature change (K)", "K", |outcome| outcome.temperature_change),
+        _ => ("Final pressure (kPa)", "kPa", |outcome| outcome.final_pressure),
+    };
+    let values: Vec<f64> = map.outcomes.iter().map(value).collect();
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+    let cells = map
+        .outcomes
+        .iter()
+        .zip(&values)
+        .map(|(outcome, value)| {
+            let color = if outcome.reactions.is_empty() && metric != PhaseMetric::FinalPressure {
+                idle
+            } else if range > 0.0 {
+                gradient((value - min) / range)
+            } else {
+                gradient(0.5)
+            };
+            (color, format!("{:.3}", value))
+        })
+        .collect();
+    let mut legend = vec![(gradient(0.0), format!("{:.3} {}", min, unit)), (gradient(1.0), format!("{:.3} {}", max, unit))];
+    if metric != PhaseMetric::FinalPressure {
+        legend.push((idle, "no reaction".to_string()));
+    }
+    (label, cells, legend)
+}
+
+fn chamber_editor(ui: &mut Ui, chamber: &mut Chamber, gases: &[Gas], room_temperature: f64) {
+    egui::Grid::new("chamber").show(ui, |ui| {
+        ui.label("Volume:");
+        ui.add(egui::DragValue::new(&mut chamber.volume).range(1.0..=f64::MAX).speed(10.0).suffix(" L"));
+        ui.end_row();
+        ui.label("Vented down to:");
+        ui.add(egui::DragValue::new(&mut chamber.pressure).range(1.0..=f64::MAX).speed(10.0).suffix(" kPa"));
+        ui.end_row();
+        ui.label("Ignition temperature:");
+        ui.add(egui::DragValue::new(&mut chamber.ignition_temperature).range(0.0..=f64::MAX).speed(1.0).suffix(" K"));
+        ui.end_row();
+        ui.label("Settle within:");
+        ui.add(egui::DragValue::new(&mut chamber.max_ticks).range(1..=100000).suffix(" ticks"));
+        ui.end_row();
+    });
+    ui.separator();
+    ui.strong("Feeds");
+    let mut removed = None;
+    egui::Grid::new("chamber_feeds").striped(true).show(ui, |ui| {
+        for (index, feed) in chamber.feeds.iter_mut().enumerate() {
+            egui::ComboBox::from_id_source(("feed", index)).selected_text(&gases[feed.gas].name).show_ui(ui, |ui| {
+                for (i, gas) in gases.iter().enumerate() {
+                    ui.selectable_value(&mut feed.gas, i, &gas.name);
+                }
+            });
+            ui.add(egui::DragValue::new(&mut feed.rate).range(0.0..=f64::MAX).speed(0.01).suffix(" mol/tick"));
+            ui.add(egui::DragValue::new(&mut feed.temperature).range(0.0..=f64::MAX).speed(1.0).suffix(" K"));
+            if ui.button("Remove").clicked() {
+                removed = Some(index);
+            }
+            ui.end_row();
+        }
+    });
+    if let Some(index) = removed {
+        chamber.feeds.remove(index);
+    }
+    if !gases.is_empty() && ui.button("Add Feed").clicked() {
+        chamber.feeds.push(Feed {
+            gas: 0,
+            rate: 1.0,
+            temperature: room_temperature,
+        });
+    }
+}
+
+fn chamber_results(ui: &mut Ui, design: &Design, gases: &[Gas], evaluated: &HashMap<String, Result<String, String>>, exports: &mut PlotExports) {
+    let color = ui.visuals().warn_fg_color;
+    for warning in &design.warnings {
+        ui.colored_label(color, warning);
+    }
+    match design.settled {
+        Some(tick) => ui.label(format!("Settled after {} ticks", tick)),
+        None => ui.colored_label(color, format!("Still changing after {} ticks; values are averaged over the final ticks", design.steps.len())),
+    };
+    egui::ScrollArea::vertical().id_source("chamber_results").show(ui, |ui| {
+        egui::Grid::new("chamber_state").striped(true).show(ui, |ui| {
+            let reactions = if design.reactions.is_empty() { "none".to_string() } else { design.reactions.join(", ") };
+            for (label, value) in [
+                ("Temperature", format!("{:.3} K", design.temperature)),
+                ("Pressure", format!("{:.3} kPa", design.pressure)),
+                ("Energy released", format!("{:.3} J/tick", design.energy_released)),
+                ("Reactions", reactions),
+            ] {
+                ui.label(label);
+                ui.label(value);
+                ui.end_row();
+            }
+        });
+        ui.separator();
+        egui::Grid::new("chamber_flows").striped(true).show(ui, |ui| {
+            for heading in ["Gas", "Fed", "Exhausted", "Net"] {
+                ui.strong(heading);
+            }
+            ui.end_row();
+            for flow in &design.flows {
+                ui.label(&gases[flow.gas].name);
+                ui.label(format!("{:.4} mol/tick", flow.fed));
+                ui.label(format!("{:.4} mol/tick", flow.exhausted));
+                ui.label(format!("{:+.4} mol/tick", flow.net()));
+                ui.end_row();
+            }
+        });
+        let column = |value: fn(&atmos::chamber::ChamberStep) -> f64| design.steps.iter().map(|step| [step.tick as f64, value(step)]).collect::<Vec<_>>();
+        Chart::new("chamber_temperature", "Tick", "Temperature (K)", vec![Series::new("Temperature", column(|step| step.temperature))]).show(ui, exports);
+        Chart::new("chamber_pressure", "Tick", "Pressure (kPa)", vec![Series::new("Pressure", column(|step| step.pressure))]).show(ui, exports);
+        ui.collapsing("Constants used", |ui| {
+            egui::Grid::new("chamber_constants").striped(true).show(ui, |ui| {
+                for name in CHAMBER_CONSTANTS {
+                    ui.label(*name);
+                    match evaluated.get(*name) {
+                        Some(Ok(value)) => ui.label(value),
+                        Some(Err(err)) => ui.colored_label(ui.visuals().error_fg_color, err),
+                        None => ui.weak("missing"),
+                    };
+                    ui.end_row();
+                }
+            });
+        });
+    });
+}
+
+fn gas_color(gas: &Gas) -> Option<egui::Color32> {
+    egui::Color32::from_hex(&format!("#{}", gas.color.as_deref()?.trim_start_matches('#'))).ok()
+}
+
+fn export_controls(ui: &mut Ui, format: &mut Format, path: &mut String, status: &Option<String>, name: &str) -> Option<ExportTarget> {
+    let mut target = None;
+    ui.horizontal(|ui| {
+        ui.label("Export as");
+        egui::ComboBox::from_id_source("export_format").selected_text(format.to_string()).show_ui(ui, |ui| {
+            for option in Format::ALL {
+                ui.selectable_value(format, option, option.to_string());
+            }
+        });
+        if ui.button("Copy to Clipboard").clicked() {
+            target = Some(ExportTarget::Clipboard);
+        }
+        ui.add(TextEdit::singleline(path).hint_text(format!("{}.{}", name, format.extension())));
+        if ui.button("Save to File").clicked() {
+            target = Some(ExportTarget::File);
+        }
+        if let Some(status) = status {
+            ui.weak(status);
+        }
+    });
+    target
+}
+
+fn copy_to_clipboard(text: String) -> Result<(), String> {
+    let mut clipboard = ClipboardContext::new().map_err(|err| format!("Error creating clipboard context: {}", err))?;
+    clipboard.set_contents(text).map_err(|err| format!("Error copying to clipboard: {}", err))
+}
+
+fn origin_label(ui: &mut Ui, origin: &Option<Origin>) {
+    if let Some(origin) = origin {
+        if let Some(reason) = &origin.offline {
+            ui.colored_label(ui.visuals().warn_fg_color, format!("Source: {}", origin)).on_hover_text(reason);
+        } else {
+            ui.label(format!("Source: {}", origin));
+        }
+    }
+}
+
+impl eframe::App for MyApp {
+    fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
+        self.poll_tasks();
+        self.plot_exports.poll(ctx);
+
+        TopBottomPanel::top("tabs").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                if ui.selectable_label(self.selected_tab == Tab::ParseConstants, "Atmos Constants").clicked() {
+                    self.selected_tab = Tab::ParseConstants;
+                }
+                if ui.selectable_label(self.selected_tab == Tab::Dependencies, "Dependencies").clicked() {
+                    self.selected_tab = Tab::Dependencies;
+                }
+                if ui.selectable_label(self.selected_tab == Tab::Calculator, "Calculator").clicked() {
+                    self.selected_tab = Tab::Calculator;
+                }
+                if ui.selectable_label(self.selected_tab == Tab::Gases, "Gases").clicked() {
+                    self.selected_tab = Tab::Gases;
+                    if !self.gases_loaded && self.gases_task.is_none() {
+                        self.fetch_and_parse_gases(ctx);
+                    }
+                }
+                if ui.selectable_label(self.selected_tab == Tab::Reactions, "Reactions").clicked() {
+                    self.selected_tab = Tab::Reactions;
+                    if !self.reactions_loaded && self.reactions_task.is_none() {
+                        self.fetch_and_parse_reactions(ctx);
+                    }
+                }
+                if ui.selectable_label(self.selected_tab == Tab::Mixture, "Mixture").clicked() {
+                    self.selected_tab = Tab::Mixture;
+                    self.load_mixture_data(ctx);
+                }
+                if ui.selectable_label(self.selected_tab == Tab::Mixing, "Mixing").clicked() {
+                    self.selected_tab = Tab::Mixing;
+                    self.load_mixture_data(ctx);
+                }
+                if ui.selectable_label(self.selected_tab == Tab::Simulation, "Simulation").clicked() {
+                    self.selected_tab = Tab::Simulation;
+                    self.load_simulation_data(ctx);
+                }
+                if ui.selectable_label(self.selected_tab == Tab::PhaseMap, "Phase Map").clicked() {
+                    self.selected_tab = Tab::PhaseMap;
+                    self.load_simulation_data(ctx);
+                }
+                if ui.selectable_label(self.selected_tab == Tab::Chamber, "Burn Chamber").clicked() {
+                    self.selected_tab = Tab::Chamber;
+                    self.load_simulation_data(ctx);
+                }
+                if ui.selectable_label(self.selected_tab == Tab::Teg, "TEG").clicked() {
+                    self.selected_tab = Tab::Teg;
+                    self.load_teg_data(ctx);
+                }
+                if ui.selectable_label(self.selected_tab == Tab::Compare, "Compare").clicked() {
+                    self.selected_tab = Tab::Compare;
+                }
+                if ui.selectable_label(self.selected_tab == Tab::History, "History").clicked() {
+                    self.selected_tab = Tab::History;
+                }
+            });
+        });
+
+        TopBottomPanel::top("source").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Source:");
+                ui.selectable_value(&mut self.source_kind, SourceKind::GitHub, "GitHub");
+                ui.selectable_value(&mut self.source_kind, SourceKind::Url, "URL");
+                ui.selectable_value(&mut self.source_kind, SourceKind::Local, "Local checkout");
+                ui.separator();
+                match self.source_kind {
+                    SourceKind::GitHub => {
+                        ui.label("Owner:");
+                        ui.add(TextEdit::singleline(&mut self.source_owner_input).hint_text(DEFAULT_OWNER).desired_width(120.0));
+                        ui.label("Repo:");
+                        ui.add(TextEdit::singleline(&mut self.source_repo_input).hint_text(DEFAULT_REPO).desired_width(120.0));
+                        ui.label("Branch/Tag/Commit:");
+                        ui.add(TextEdit::singleline(&mut self.source_ref_input).hint_text(DEFAULT_REF).desired_width(120.0));
+                    }
+                    SourceKind::Url => {
+                        ui.label("Base URL:");
+                        ui.text_edit_singleline(&mut self.source_url_input);
+                    }
+                    SourceKind::Local => {
+                        ui.label("Path:");
+                        ui.text_edit_singleline(&mut self.local_path_input);
+                    }
+                }
+                if ui.button("Apply").clicked() {
+                    self.apply_source_inputs(ctx);
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(format!("Selected: {}", self.source));
+                if let Some(err) = &self.source_error {
+                    ui.colored_label(ui.visuals().error_fg_color, err.to_string());
+                }
+            });
+        });
+
+        CentralPanel::default().show(ctx, |ui| {
+            match self.selected_tab {
+                Tab::ParseConstants => {
+                    if self.constants_task.is_some() {
+                        progress(ui, &mut self.constants_task, "constants");
+                    } else if self.constant_list.is_empty() {
+                        if error_panel(ui, &self.constants_error, "constants") || ui.button("Load Constants").clicked() {
+                            self.fetch_and_parse_constants(ctx);
+                        }
+                    } else {
+                        origin_label(ui, &self.constants_origin);
+                        ui.horizontal(|ui| {
+                            if ui.button("Sort Ascending").clicked() {
+                                self.sort_order = SortOrder::Ascending;
+                                self.sort_and_filter_constants();
+                            }
+                            if ui.button("Sort Descending").clicked() {
+                                self.sort_order = SortOrder::Descending;
+                                self.sort_and_filter_constants();
+                            }
+                            ui.label("Search:");
+                            if ui.text_edit_singleline(&mut self.search_query).changed() {
+                                self.filter_constants();
+                            }
+                        });
+                        if let Some(target) = export_controls(ui, &mut self.export_format, &mut self.export_path_input, &self.export_status, "constants") {
+                            self.export(Table::constants(&self.filtered_constants, &self.evaluated_constants), "constants", target);
+                        }
+
+                        let selected = self.selected_constant.as_ref().and_then(|name| self.constant_list.iter().find(|constant| &constant.name == name));
+                        if let Some(constant) = selected {
+                            if constant_details(ui, constant, &self.constants_origin) {
+                                self.selected_constant = None;
+                            }
+                        }
+
+                        egui::ScrollArea::both().show(ui, |ui| {
+                            TableBuilder::new(ui)
+                                .striped(true)
+                                .resizable(true)
+                                .column(Column::initial(150.0).resizable(true))
+                                .column(Column::initial(60.0).resizable(true))
+                                .column(Column::initial(300.0).resizable(true))
+                                .column(Column::initial(150.0).resizable(true))
+                                .column(Column::remainder().resizable(true))
+                                .header(20.0, |mut header| {
+                                    header.col(|ui| {
+                                        ui.heading("Constant Name");
+                                    });
+                                    header.col(|ui| {
+                                        ui.heading("Type");
+                                    });
+                                    header.col(|ui| {
+                                        ui.heading("Value");
+                                    });
+                                    header.col(|ui| {
+                                        ui.heading("Evaluated");
+                                    });
+                                    header.col(|ui| {
+                                        ui.heading("Depends On");
+                                    });
+                                })
+                                .body(|mut body| {
+                                    for constant in &self.filtered_constants {
+                                        body.row(20.0, |mut row| {
+                                            row.col(|ui| {
+                                                let is_selected = self.selected_constant.as_ref() == Some(&constant.name);
+                                                let mut label = ui.selectable_label(is_selected, &constant.name);
+                                                if let Some(description) = &constant.description {
+                                                    label = label.on_hover_text(description);
+                                                }
+                                                if label.clicked() {
+                                                    self.selected_constant = if is_selected { None } else { Some(constant.name.clone()) };
+                                                }
+                                            });
+                                            row.col(|ui| {
+                                                if constant.readonly {
+                                                    ui.label(format!("{} (readonly)", constant.ty));
+                                                } else {
+                                                    ui.label(&constant.ty);
+                                                }
+                                            });
+                                            row.col(|ui| {
+                                                ui.label(&constant.initializer);
+                                            });
+                                            row.col(|ui| match self.evaluated_constants.get(&constant.name) {
+                                                Some(Ok(value)) => {
+                                                    ui.label(value);
+                                                }
+                                                Some(Err(err)) => {
+                                                    ui.colored_label(ui.visuals().error_fg_color, "error").on_hover_text(err);
+                                                }
+                                                None => {}
+                                            });
+                                            row.col(|ui| {
+                                                ui.label(constant.references.join(", "));
+                                            });
+                                        });
+                                    }
+                                });
+                        });
+                    }
+                }
+                Tab::Dependencies => {
+                    ui.heading("Dependencies");
+                    if self.constants_task.is_some() {
+                        progress(ui, &mut self.constants_task, "constants");
+                    } else if self.constant_list.is_empty() {
+                        if error_panel(ui, &self.constants_error, "constants") || ui.button("Load Constants").clicked() {
+                            self.fetch_and_parse_constants(ctx);
+                        }
+                    } else {
+                        origin_label(ui, &self.constants_origin);
+                        dependency_problems(ui, &self.dependency_graph, &mut self.selected_constant);
+                        ui.separator();
+                        ui.columns(2, |columns| {
+                            columns[0].horizontal(|ui| {
+                                ui.label("Search:");
+                                if ui.text_edit_singleline(&mut self.search_query).changed() {
+                                    self.filter_constants();
+                                }
+                            });
+                            egui::ScrollArea::vertical().id_source("dependency_list").show(&mut columns[0], |ui| {
+                                for constant in &self.filtered_constants {
+                                    let is_selected = self.selected_constant.as_ref() == Some(&constant.name);
+                                    let mut label = ui.selectable_label(is_selected, &constant.name);
+                                    if self.dependency_graph.in_cycle(&constant.name) {
+                                        label = label.on_hover_text("Part of a circular reference");
+                                    }
+                                    if label.clicked() {
+                                        self.selected_constant = Some(constant.name.clone());
+                                    }
+                                }
+                            });
+
+                            let ui = &mut columns[1];
+                            let Some(name) = self.selected_constant.clone().filter(|name| self.dependency_graph.contains(name)) else {
+                                ui.label("Select a constant to see its dependencies.");
+                                return;
+                            };
+                            egui::ScrollArea::vertical().id_source("dependency_tree").show(ui, |ui| {
+                                ui.strong(&name);
+                                if let Some(Ok(value)) = self.evaluated_constants.get(&name) {
+                                    ui.label(format!("= {}", value));
+                                }
+                                ui.separator();
+                                ui.label("Depends on:");
+                                if self.dependency_graph.dependencies(&name).is_empty() {
+                                    ui.weak("nothing");
+                                }
+                                dependency_tree(ui, &self.dependency_graph, &mut vec![name.clone()], false, &mut self.selected_constant);
+                                ui.separator();
+                                ui.label("Used by:");
+                                if self.dependency_graph.dependents(&name).is_empty() {
+                                    ui.weak("nothing");
+                                }
+                                dependency_tree(ui, &self.dependency_graph, &mut vec![name.clone()], true, &mut self.selected_constant);
+                            });
+                        });
+                    }
+                }
+                Tab::Calculator => {
+                    ui.heading("Calculator");
+                    ui.horizontal(|ui| {
+                        ui.label("Expression:");
+                        ui.text_edit_singleline(&mut self.calculator_input);
+                    });
+                    if ui.button("Calculate").clicked() {
+                        let evaluator = self.evaluator();
+                        let context = evaluator.create_evaluation_context();
+                        self.calculator_output = match evaluator.resolve_expression(&self.calculator_input, &mut context.clone()) {
+                            Ok(result) => result,
+                            Err(err) => err,
+                        };
+                    }
+                    ui.label(format!("Result: {}", self.calculator_output));
+
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        ui.label("Sweep");
+                        ui.add(TextEdit::singleline(&mut self.sweep_variable).hint_text("variable").desired_width(120.0));
+                        ui.label("from");
+                        ui.add(egui::DragValue::new(&mut self.sweep_from));
+                        ui.label("to");
+                        ui.add(egui::DragValue::new(&mut self.sweep_to));
+                        ui.label("steps");
+                        ui.add(egui::DragValue::new(&mut self.sweep_steps).range(2..=10000));
+                        let variable = self.sweep_variable.trim().to_string();
+                        if ui.add_enabled(!variable.is_empty(), egui::Button::new("Plot")).clicked() {
+                            let values = sweep_values(self.sweep_from, self.sweep_to, self.sweep_steps);
+                            let results = self.evaluator().sweep(&self.calculator_input, &variable, &values);
+                            self.sweep = Some(Sweep {
+                                expression: self.calculator_input.clone(),
+                                variable,
+                                values,
+                                results,
+                            });
+                        }
+                    });
+                    if let Some(sweep) = &self.sweep {
+                        let errors: Vec<&String> = sweep.results.iter().filter_map(|result| result.as_ref().err()).collect();
+                        if let Some(err) = errors.first() {
+                            ui.colored_label(ui.visuals().error_fg_color, format!("{} of {} points failed: {}", errors.len(), sweep.values.len(), err));
+                        }
+                        let points = sweep.values.iter().zip(&sweep.results).filter_map(|(x, y)| Some([*x, *y.as_ref().ok()?])).collect();
+                        Chart::new("sweep", &sweep.variable, "Result", vec![Series::new(sweep.expression.clone(), points)]).show(ui, &mut self.plot_exports);
+                    }
+
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        ui.label("Variable set:");
+                        let mut selected = None;
+                        egui::ComboBox::from_id_source("variable_set").selected_text(&self.variable_set).show_ui(ui, |ui| {
+                            let mut names: Vec<&String> = self.variable_sets.keys().chain([&self.variable_set]).collect();
+                            names.sort();
+                            for name in names {
+                                if ui.selectable_label(*name == self.variable_set, name).clicked() && *name != self.variable_set {
+                                    selected = Some(name.clone());
+                                }
+                            }
+                        });
+                        if let Some(name) = selected {
+                            self.select_variable_set(name);
+                        }
+                        ui.add(TextEdit::singleline(&mut self.new_variable_set_input).hint_text("new set name"));
+                        let name = self.new_variable_set_input.trim().to_string();
+                        if ui.add_enabled(!name.is_empty() && name != self.variable_set, egui::Button::new("Save As")).clicked() {
+                            let variables = self.user_variables.clone();
+                            self.select_variable_set(name);
+                            self.user_variables = variables;
+                            self.evaluate_constants();
+                            self.new_variable_set_input.clear();
+                        }
+                        if ui.add_enabled(!self.variable_sets.is_empty(), egui::Button::new("Delete Set")).clicked() {
+                            self.delete_variable_set();
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.add(TextEdit::singleline(&mut self.variables_path_input).hint_text(format!("{}.yml", self.variable_set)));
+                        if ui.button("Import").clicked() {
+                            self.import_variables();
+                        }
+                        if ui.button("Export").clicked() {
+                            self.export_variables();
+                        }
+                        if let Some(status) = &self.variables_status {
+                            ui.weak(status);
+                        }
+                    });
+
+                    ui.separator();
+                    ui.heading("Create New Variable");
+                    ui.horizontal(|ui| {
+                        ui.label("Name:");
+                        ui.text_edit_singleline(&mut self.new_variable_name);
+                        ui.label("Value:");
+                        ui.text_edit_singleline(&mut self.new_variable_value);
+                    });
+                    if ui.button("Add Variable").clicked() {
+                        self.user_variables.insert(self.new_variable_name.clone(), self.new_variable_value.clone());
+                        self.new_variable_name.clear();
+                        self.new_variable_value.clear();
+                        self.evaluate_constants();
+                    }
+
+                    let mut to_delete = Vec::new();
+                    egui::ScrollArea::both().show(ui, |ui| {
+                        TableBuilder::new(ui)
+                            .striped(true)
+                            .resizable(true)
+                            .column(Column::initial(150.0).resizable(true))
+                            .column(Column::remainder().resizable(true))
+                            .column(Column::initial(60.0).resizable(false))
+                            .header(20.0, |mut header| {
+                                header.col(|ui| {
+                                    ui.heading("Variable Name");
+                                });
+                                header.col(|ui| {
+                                    ui.heading("Value");
+                                });
+                                header.col(|ui| {
+                                    ui.heading("");
+                                });
+                            })
+                            .body(|mut body| {
+                                for (name, value) in &self.user_variables {
+                                    body.row(20.0, |mut row| {
+                                        row.col(|ui| {
+                                            ui.label(name);
+                                        });
+                                        row.col(|ui| {
+                                            ui.label(value);
+                                        });
+                                        row.col(|ui| {
+                                            if ui.button("Delete").clicked() {
+                                                to_delete.push(name.clone());
+                                            }
+                                        });
+                                    });
+                                }
+                            });
+                    });
+
+                    for name in to_delete {
+                        self.delete_user_variable(&name);
+                    }
+                }
+                Tab::Gases => {
+                    ui.heading("Gases");
+                    if self.gases_task.is_some() {
+                        progress(ui, &mut self.gases_task, "gases");
+                    } else if ui.button("Load Gases").clicked() || error_panel(ui, &self.gases_error, "gases") {
+                        self.fetch_and_parse_gases(ctx);
+                    }
+                    origin_label(ui, &self.gases_origin);
+                    if !self.gases.is_empty() {
+                        if let Some(target) = export_controls(ui, &mut self.export_format, &mut self.export_path_input, &self.export_status, "gases") {
+                            self.export(Table::gases(&self.gases), "gases", target);
+                        }
+                    }
+                    egui::ScrollArea::both().show(ui, |ui| {
+                        TableBuilder::new(ui)
+                            .striped(true)
+                            .resizable(true)
+                            .column(Column::initial(200.0).resizable(true))
+                            .column(Column::initial(100.0).resizable(true))
+                            .column(Column::initial(100.0).resizable(true))
+                            .column(Column::initial(100.0).resizable(true))
+                            .header(20.0, |mut header| {
+                                header.col(|ui| {
+                                    ui.heading("Gas Name");
+                                });
+                                header.col(|ui| {
+                                    ui.heading("Specific Heat");
+                                });
+                                header.col(|ui| {
+                                    ui.heading("Heat Capacity Ratio");
+                                });
+                                header.col(|ui| {
+                                    ui.heading("Molar Mass");
+                                });
+                            })
+                            .body(|mut body| {
+                                for gas in &self.gases {
+                                    body.row(20.0, |mut row| {
+                                        row.col(|ui| {
+                                            ui.label(&gas.name);
+                                        });
+                                        row.col(|ui| {
+                                            ui.label(gas.specific_heat.map_or("n/a".to_string(), |v| v.to_string()));
+                                        });
+                                        row.col(|ui| {
+                                            ui.label(gas.heat_capacity_ratio.map_or("n/a".to_string(), |v| v.to_string()));
+                                        });
+                                        row.col(|ui| {
+                                            ui.label(gas.molar_mass.map_or("n/a".to_string(), |v| v.to_string()));
+                                        });
+                                    });
+                                }
+                            });
+                        if !self.gases.is_empty() {
+                            ui.separator();
+                            ui.heading("Details");
+                            for gas in &self.gases {
+                                gas_details(ui, gas);
+                            }
+                        }
+                    });
+                }
+                Tab::Reactions => {
+                    ui.heading("Reactions");
+                    if self.reactions_task.is_some() {
+                        progress(ui, &mut self.reactions_task, "reactions");
+                    } else if ui.button("Load Reactions").clicked() || error_panel(ui, &self.reactions_error, "reactions") {
+                        self.fetch_and_parse_reactions(ctx);
+                    }
+                    origin_label(ui, &self.reactions_origin);
+                    if !self.reactions.is_empty() {
+                        if let Some(target) = export_controls(ui, &mut self.export_format, &mut self.export_path_input, &self.export_status, "reactions") {
+                            self.export(Table::reactions(&self.reactions, &self.gas_names), "reactions", target);
+                        }
+                    }
+                    for warning in requirement_warnings(&self.reactions, &self.gas_names) {
+                        ui.colored_label(ui.visuals().warn_fg_color, warning);
+                    }
+                    egui::ScrollArea::both().show(ui, |ui| {
+                        TableBuilder::new(ui)
+                            .striped(true)
+                            .resizable(true)
+                            .column(Column::initial(200.0).resizable(true))
+                            .column(Column::initial(60.0).resizable(true))
+                            .column(Column::initial(100.0).resizable(true))
+                            .column(Column::initial(100.0).resizable(true))
+                            .column(Column::initial(200.0).resizable(true))
+                            .column(Column::initial(100.0).resizable(true))
+                            .header(20.0, |mut header| {
+                                header.col(|ui| {
+                                    ui.heading("Reaction ID");
+                                });
+                                header.col(|ui| {
+                                    ui.heading("Priority");
+                                });
+                                header.col(|ui| {
+                                    ui.heading("Minimum Temperature");
+                                });
+                                header.col(|ui| {
+                                    ui.heading("Maximum Temperature");
+                                });
+                                header.col(|ui| {
+                                    ui.heading("Minimum Requirements");
+                                });
+                                header.col(|ui| {
+                                    ui.heading("Effects");
+                                });
+                            })
+                            .body(|mut body| {
+                                for reaction in &self.reactions {
+                                    let requirements = reaction.requirements(&self.gas_names);
+                                    body.row(20.0 * reaction.effects.len().max(requirements.len()).max(1) as f32, |mut row| {
+                                        row.col(|ui| {
+                                            ui.label(&reaction.id);
+                                        });
+                                        row.col(|ui| {
+                                            ui.label(reaction.priority.map_or("n/a".to_string(), |v| v.to_string()));
+                                        });
+                                        row.col(|ui| {
+                                            ui.label(reaction.minimum_temperature.map_or("n/a".to_string(), |v| v.to_string()));
+                                        });
+                                        row.col(|ui| {
+                                            ui.label(reaction.maximum_temperature.map_or("n/a".to_string(), |v| v.to_string()));
+                                        });
+                                        row.col(|ui| {
+                                            for requirement in &requirements {
+                                                ui.label(requirement.to_string());
+                                            }
+                                        });
+                                        row.col(|ui| {
+                                            for effect in &reaction.effects {
+                                                let label = ui.label(effect.describe(&self.gas_names));
+                                                if let Effect::Unknown { value, .. } = effect {
+                                                    label.on_hover_text(serde_yaml::to_string(value).unwrap_or_default());
+                                                }
+                                            }
+                                        });
+                                    });
+                                }
+                            });
+                    });
+                }
+                Tab::Mixture => {
+                    ui.heading("Mixture");
+                    if let Some(physics) = self.mixture_physics(ui, ctx) {
+                        let gases = &self.gases;
+                        let mixture = self
+                            .mixture
+                            .get_or_insert_with(|| Mixture::new(gases.len(), physics.room_temperature, physics.cell_volume));
+                        amount_mode(ui, &mut self.mixture_by_pressure);
+                        ui.separator();
+                        ui.columns(2, |columns| {
+                            mixture_editor(&mut columns[0], "mixture", mixture, gases, &physics, self.mixture_by_pressure);
+                            mixture_properties(&mut columns[1], "mixture", &mixture.properties(gases, &physics));
+                        });
+                    }
+                }
+                Tab::Mixing => {
+                    ui.heading("Mixing");
+                    if let Some(physics) = self.mixture_physics(ui, ctx) {
+                        let gases = &self.gases;
+                        let new = || Mixture::new(gases.len(), physics.room_temperature, physics.cell_volume);
+                        let giver = self.mixing_giver.get_or_insert_with(new);
+                        let receiver = self.mixing_receiver.get_or_insert_with(new);
+                        ui.horizontal(|ui| {
+                            amount_mode(ui, &mut self.mixture_by_pressure);
+                            ui.separator();
+                            ui.label("Result volume:");
+                            ui.selectable_value(&mut self.mixing_combined_volume, false, "Receiver (dump into)");
+                            ui.selectable_value(&mut self.mixing_combined_volume, true, "Combined (connect)");
+                        });
+                        ui.separator();
+                        let mut result = receiver.clone();
+                        result.merge(giver, gases, &physics);
+                        if self.mixing_combined_volume {
+                            result.volume += giver.volume;
+                        }
+                        ui.columns(3, |columns| {
+                            columns[0].strong("Giver (canister A)");
+                            mixture_editor(&mut columns[0], "giver", giver, gases, &physics, self.mixture_by_pressure);
+                            columns[1].strong("Receiver (tank B)");
+                            mixture_editor(&mut columns[1], "receiver", receiver, gases, &physics, self.mixture_by_pressure);
+                            columns[2].strong("Result");
+                            mixture_properties(&mut columns[2], "mixing", &result.properties(gases, &physics));
+                        });
+                    }
+                }
+                Tab::Simulation => {
+                    ui.heading("Simulation");
+                    progress(ui, &mut self.reactions_task, "reactions");
+                    if error_panel(ui, &self.reactions_error, "reactions") {
+                        self.fetch_and_parse_reactions(ctx);
+                    }
+                    if let Some(physics) = self.mixture_physics(ui, ctx) {
+                        let gases = &self.gases;
+                        let mixture = self
+                            .simulation_mixture
+                            .get_or_insert_with(|| Mixture::new(gases.len(), physics.room_temperature, physics.cell_volume));
+                        ui.horizontal(|ui| {
+                            amount_mode(ui, &mut self.mixture_by_pressure);
+                            ui.separator();
+                            ui.label("Ticks:");
+                            ui.add(egui::DragValue::new(&mut self.simulation_ticks).range(1..=10000));
+                            ui.checkbox(&mut self.simulation_tile, "On a tile").on_hover_text("Water vapor only condenses into puddles on tiles");
+                            let ready = self.reactions_loaded;
+                            if ui.add_enabled(ready, egui::Button::new("Simulate")).clicked() {
+                                let mut simulation = Simulation::new(gases, &self.gas_names, &self.reactions, physics, &self.evaluated_constants);
+                                simulation.tile = self.simulation_tile;
+                                self.simulation_run = Some(simulation.run(mixture.clone(), self.simulation_ticks));
+                            }
+                        });
+                        ui.separator();
+                        ui.columns(2, |columns| {
+                            mixture_editor(&mut columns[0], "simulation", mixture, gases, &physics, self.mixture_by_pressure);
+                            if let Some(run) = &self.simulation_run {
+                                simulation_results(&mut columns[1], run, gases, &mut self.plot_exports);
+                            }
+                        });
+                    }
+                }
+                Tab::PhaseMap => {
+                    ui.heading("Phase Map");
+                    progress(ui, &mut self.reactions_task, "reactions");
+                    if error_panel(ui, &self.reactions_error, "reactions") {
+                        self.fetch_and_parse_reactions(ctx);
+                    }
+                    progress(ui, &mut self.phase_task, "phase map");
+                    if let Some(physics) = self.mixture_physics(ui, ctx) {
+                        let gases = &self.gases;
+                        let (x, y) = self.phase_axes.get_or_insert_with(|| default_phase_axes(gases));
+                        let mixture = self.phase_mixture.get_or_insert_with(|| {
+                            let mut mixture = Mixture::new(gases.len(), physics.room_temperature, physics.cell_volume);
+                            for axis in [&*x, &*y] {
+                                if let Parameter::Ratio { gas, other } = axis.parameter {
+                                    mixture.moles[gas] = 50.0;
+                                    mixture.moles[other] = 50.0;
+                                }
+                            }
+                            mixture
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label("X:");
+                            phase_axis_editor(ui, "phase_x", x, gases);
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label("Y:");
+                            phase_axis_editor(ui, "phase_y", y, gases);
+                        });
+                        let invalid = [&*x, &*y].into_iter().find_map(|axis| axis.parameter.validate(gases).err());
+                        if let Some(err) = &invalid {
+                            ui.colored_label(ui.visuals().error_fg_color, format!("Can't run the map: {}", err));
+                        }
+                        ui.horizontal(|ui| {
+                            amount_mode(ui, &mut self.mixture_by_pressure);
+                            ui.separator();
+                            ui.label("Ticks:");
+                            ui.add(egui::DragValue::new(&mut self.phase_ticks).range(1..=1000));
+                            ui.label("Show:");
+                            ui.selectable_value(&mut self.phase_metric, PhaseMetric::EnergyReleased, "Energy released");
+                            ui.selectable_value(&mut self.phase_metric, PhaseMetric::TemperatureChange, "Temperature change");
+                            ui.selectable_value(&mut self.phase_metric, PhaseMetric::FinalPressure, "Final pressure");
+                            ui.selectable_value(&mut self.phase_metric, PhaseMetric::Reactions, "Reactions");
+                            let ready = self.reactions_loaded && invalid.is_none();
+                            if ui.add_enabled(ready, egui::Button::new("Run")).clicked() {
+                                let (x, y, ticks, x_label, y_label) = (*x, *y, self.phase_ticks, x.parameter.label(gases), y.parameter.label(gases));
+                                let (mixture, gases, names, reactions, evaluated) =
+                                    (mixture.clone(), gases.clone(), self.gas_names.clone(), self.reactions.clone(), self.evaluated_constants.clone());
+                                self.phase_task = Some(Task::spawn_cancellable(ctx, move |cancelled| {
+                                    let simulation = Simulation::new(&gases, &names, &reactions, physics, &evaluated);
+                                    let map = phase_map_until(&simulation, &mixture, x, y, ticks, cancelled)?;
+                                    Some(Phase { map, x_label, y_label })
+                                }));
+                            }
+                        });
+                        for axis in [&*x, &*y] {
+                            if let Parameter::Ratio { gas, other } = axis.parameter {
+                                if mixture.moles[gas] + mixture.moles[other] <= 0.0 {
+                                    ui.colored_label(
+                                        ui.visuals().warn_fg_color,
+                                        format!("The ratio only splits the moles already present; add some {} or {} to the mixture", gases[gas].name, gases[other].name),
+                                    );
+                                }
+                            }
+                        }
+                        ui.separator();
+                        ui.columns(2, |columns| {
+                            columns[0].label("Base mixture");
+                            mixture_editor(&mut columns[0], "phase", mixture, gases, &physics, self.mixture_by_pressure);
+                            if let Some(phase) = &self.phase {
+                                let color = columns[1].visuals().warn_fg_color;
+                                for warning in &phase.map.warnings {
+                                    columns[1].colored_label(color, warning);
+                                }
+                                let (label, cells, legend) = phase_cells(&phase.map, self.phase_metric);
+                                Heatmap::new("phase_map", [&phase.x_label, &phase.y_label, label], &phase.map.x, &phase.map.y, cells)
+                                    .legend(legend)
+                                    .show(&mut columns[1], &mut self.plot_exports);
+                            }
+                        });
+                    }
+                }
+                Tab::Chamber => {
+                    ui.heading("Burn Chamber");
+                    progress(ui, &mut self.reactions_task, "reactions");
+                    if error_panel(ui, &self.reactions_error, "reactions") {
+                        self.fetch_and_parse_reactions(ctx);
+                    }
+                    progress(ui, &mut self.chamber_task, "chamber design");
+                    if let Some(physics) = self.mixture_physics(ui, ctx) {
+                        let gases = &self.gases;
+                        let evaluated = &self.evaluated_constants;
+                        let chamber = self.chamber.get_or_insert_with(|| {
+                            let feed = |name: &str, rate: f64| {
+                                Some(Feed {
+                                    gas: gases.iter().position(|gas| gas.matches(name))?,
+                                    rate,
+                                    temperature: physics.room_temperature,
+                                })
+                            };
+                            Chamber {
+                                volume: physics.cell_volume,
+                                pressure: 1000.0,
+                                ignition_temperature: evaluated
+                                    .get("PlasmaUpperTemperature")
+                                    .and_then(|value| value.as_ref().ok()?.parse().ok())
+                                    .unwrap_or(1000.0),
+                                feeds: [feed("oxygen", 2.0), feed("plasma", 0.2)].into_iter().flatten().collect(),
+                                max_ticks: 1000,
+                            }
+                        });
+                        ui.columns(2, |columns| {
+                            chamber_editor(&mut columns[0], chamber, gases, physics.room_temperature);
+                            if !self.reactions_loaded {
+                                return;
+                            }
+                            // The design can take thousands of ticks, so it runs off the UI thread and the
+                            // previous result stays on screen until the new one arrives. Replacing the task
+                            // cancels the run for the old values, so dragging a value doesn't pile up workers.
+                            if self.chamber_requested.as_ref() != Some(&*chamber) {
+                                self.chamber_requested = Some(chamber.clone());
+                                let (chamber, gases, names, reactions, evaluated) =
+                                    (chamber.clone(), gases.clone(), self.gas_names.clone(), self.reactions.clone(), evaluated.clone());
+                                self.chamber_task = Some(Task::spawn_cancellable(ctx, move |cancelled| {
+                                    chamber.design_until(&Simulation::new(&gases, &names, &reactions, physics, &evaluated), cancelled)
+                                }));
+                            }
+                            match &self.chamber_design {
+                                Some(Err(err)) => {
+                                    columns[1].colored_label(columns[1].visuals().error_fg_color, err);
+                                }
+                                Some(Ok(design)) => {
+                                    chamber_results(&mut columns[1], design, gases, evaluated, &mut self.plot_exports);
+                                }
+                                None => {}
+                            }
+                        });
+                    }
+                }
+                Tab::Teg => {
+                    ui.heading("TEG");
+                    progress(ui, &mut self.teg_task, "TEG prototype");
+                    if error_panel(ui, &self.teg_error, "TEG prototype") {
+                        self.fetch_generator(ctx);
+                    }
+                    if let Some(physics) = self.mixture_physics(ui, ctx) {
+                        let gases = &self.gases;
+                        let new = || Mixture::new(gases.len(), physics.room_temperature, physics.cell_volume);
+                        let hot = self.teg_hot.get_or_insert_with(new);
+                        let cold = self.teg_cold.get_or_insert_with(new);
+                        let generator = self.teg_generator.unwrap_or_default();
+                        ui.horizontal(|ui| {
+                            origin_label(ui, &self.teg_origin);
+                            ui.label(format!("Thermal efficiency {}, power factor {}", generator.thermal_efficiency, generator.power_factor));
+                            if self.teg_loaded && self.teg_generator.is_none() {
+                                ui.colored_label(ui.visuals().warn_fg_color, "(no TEG prototype found, using the defaults)");
+                            }
+                        });
+                        ui.horizontal(|ui| {
+                            amount_mode(ui, &mut self.mixture_by_pressure);
+                            ui.separator();
+                            ui.label("Update interval:");
+                            ui.add(egui::DragValue::new(&mut self.teg_interval).range(0.001..=f64::MAX).speed(0.01).suffix(" s"))
+                                .on_hover_text("Seconds between atmos device updates, used to turn energy per update into power");
+                        });
+                        ui.separator();
+                        let transfer = generator.transfer(hot, cold, gases, &physics);
+                        ui.columns(3, |columns| {
+                            columns[0].strong("Hot loop (gas through the hot circulator per update)");
+                            mixture_editor(&mut columns[0], "teg_hot", hot, gases, &physics, self.mixture_by_pressure);
+                            columns[1].strong("Cold loop (gas through the cold circulator per update)");
+                            mixture_editor(&mut columns[1], "teg_cold", cold, gases, &physics, self.mixture_by_pressure);
+                            columns[2].strong("Output");
+                            let Some(transfer) = transfer else {
+                                columns[2].weak("Both loops need gas, and the hot loop must be hotter than the cold loop.");
+                                return;
+                            };
+                            egui::Grid::new("teg_output").striped(true).show(&mut columns[2], |ui| {
+                                for (label, value) in [
+                                    ("Power", format!("{:.3} W", transfer.electrical_energy / self.teg_interval)),
+                                    ("Electrical energy", format!("{:.3} J per update", transfer.electrical_energy)),
+                                    ("Heat transferred", format!("{:.3} J", transfer.heat_transferred)),
+                                    ("Carnot efficiency", format!("{:.2}%", transfer.carnot_efficiency * 100.0)),
+                                    ("Efficiency", format!("{:.2}%", transfer.efficiency * 100.0)),
+                                    ("Hot outlet", format!("{:.3} K", transfer.hot_outlet_temperature)),
+                                    ("Cold outlet", format!("{:.3} K", transfer.cold_outlet_temperature)),
+                                ] {
+                                    ui.label(label);
+                                    ui.label(value);
+                                    ui.end_row();
+                                }
+                            });
+                        });
+                    }
+                }
+                Tab::Compare => {
+                    ui.heading("Compare");
+                    ui.horizontal(|ui| {
+                        ui.label("Old:");
+                        ui.add(TextEdit::singleline(&mut self.compare_old_input).hint_text("owner/repo@ref, @ref, URL or local path"));
+                        ui.label("New:");
+                        let current = self.source.to_string();
+                        ui.add(TextEdit::singleline(&mut self.compare_new_input).hint_text(current));
+                        if ui.add_enabled(self.compare_task.is_none(), egui::Button::new("Compare")).clicked() {
+                            self.compare_sources(ctx);
+                        }
+                    });
+                    progress(ui, &mut self.compare_task, "both sources");
+                    if error_panel(ui, &self.compare_error, "comparison") {
+                        self.compare_sources(ctx);
+                    }
+
+                    if let Some((old, new)) = &self.compare_origins {
+                        ui.label(format!("From {}", old));
+                        ui.label(format!("To {}", new));
+                        ui.horizontal(|ui| {
+                            if ui.button("Copy Report").clicked() {
+                                self.report_status = copy_to_clipboard(self.report()).err();
+                            }
+                            ui.add(TextEdit::singleline(&mut self.report_path_input).hint_text("atmos-changes.md"));
+                            if ui.button("Save Report").clicked() {
+                                if self.report_path_input.trim().is_empty() {
+                                    self.report_path_input = "atmos-changes.md".to_string();
+                                }
+                                self.save_report();
+                            }
+                            if let Some(status) = &self.report_status {
+                                ui.label(status);
+                            }
+                        });
+                        if self.differences.is_empty() {
+                            ui.label("No differences.");
+                        }
+
+                        egui::ScrollArea::both().show(ui, |ui| {
+                            TableBuilder::new(ui)
+                                .striped(true)
+                                .resizable(true)
+                                .column(Column::initial(70.0).resizable(true))
+                                .column(Column::initial(200.0).resizable(true))
+                                .column(Column::initial(100.0).resizable(true))
+                                .column(Column::initial(70.0).resizable(true))
+                                .column(Column::initial(200.0).resizable(true))
+                                .column(Column::initial(200.0).resizable(true))
+                                .column(Column::initial(120.0).resizable(true))
+                                .column(Column::remainder().resizable(true))
+                                .header(20.0, |mut header| {
+                                    for title in ["Dataset", "Name", "Field", "Change", "Old", "New", "Old Value", "New Value"] {
+                                        header.col(|ui| {
+                                            ui.heading(title);
+                                        });
+                                    }
+                                })
+                                .body(|mut body| {
+                                    for difference in &self.differences {
+                                        body.row(20.0, |mut row| {
+                                            row.col(|ui| {
+                                                ui.label(difference.dataset.to_string());
+                                            });
+                                            row.col(|ui| {
+                                                ui.label(&difference.name);
+                                            });
+                                            row.col(|ui| {
+                                                ui.label(&difference.field);
+                                            });
+                                            row.col(|ui| {
+                                                let color = match difference.change {
+                                                    Change::Added => egui::Color32::from_rgb(90, 170, 90),
+                                                    Change::Removed => ui.visuals().error_fg_color,
+                                                    Change::Changed => ui.visuals().warn_fg_color,
+                                                };
+                                                ui.colored_label(color, difference.change.to_string());
+                                            });
+                                            for value in [&difference.old, &difference.new, &difference.old_value, &difference.new_value] {
+                                                row.col(|ui| {
+                                                    ui.label(value.as_deref().unwrap_or(""));
+                                                });
+                                            }
+                                        });
+                                    }
+                                });
+                        });
+                    }
+                }
+                Tab::History => {
+                    ui.heading("History");
+                    ui.horizontal(|ui| {
+                        ui.label("Checkout:");
+                        let hint = match &self.source {
+                            DataSource::Local(root) => root.display().to_string(),
+                            _ => "path to a local Space Station 14 checkout".to_string(),
+                        };
+                        ui.add(TextEdit::singleline(&mut self.history_path_input).hint_text(hint));
+                        if ui.add_enabled(self.history_task.is_none(), egui::Button::new("Load History")).clicked() {
+                            self.load_history(ctx);
+                        }
+                    });
+                    progress(ui, &mut self.history_task, "git history");
+                    if error_panel(ui, &self.history_error, "git history") {
+                        self.load_history(ctx);
+                    }
+
+                    if let Some(history) = &self.history {
+                        ui.label(format!("{} commits", history.revisions.len()));
+                        ui.columns(2, |columns| {
+                            columns[0].horizontal(|ui| {
+                                ui.label("Search:");
+                                ui.text_edit_singleline(&mut self.history_query);
+                            });
+                            let query = self.history_query.to_lowercase();
+                            egui::ScrollArea::vertical().id_source("timeline_list").show(&mut columns[0], |ui| {
+                                for (i, timeline) in history.timelines.iter().enumerate() {
+                                    if !timeline.name.to_lowercase().contains(&query) {
+                                        continue;
+                                    }
+                                    let label = format!("{} {}.{} ({} changes)", timeline.dataset, timeline.name, timeline.field, timeline.points.len().saturating_sub(1));
+                                    if ui.selectable_label(self.selected_timeline == Some(i), label).clicked() {
+                                        self.selected_timeline = Some(i);
+                                    }
+                                }
+                            });
+
+                            let ui = &mut columns[1];
+                            let Some(timeline) = self.selected_timeline.and_then(|i| history.timelines.get(i)) else {
+                                ui.label("Select a constant, gas or reaction field to see its timeline.");
+                                return;
+                            };
+                            ui.strong(format!("{}.{}", timeline.name, timeline.field));
+                            let points = timeline.points.iter().filter_map(|point| Some([point.revision as f64, point.number()?])).collect();
+                            let end = history.revisions.len().saturating_sub(1) as f64;
+                            Chart::new("history", "Commit", &timeline.field, vec![Series::new(timeline.name.clone(), points).steps(end)])
+                                .x_text(|x| {
+                                    let revision = x.round().max(0.0) as usize;
+                                    history.revisions.get(revision).map_or_else(String::new, |revision| {
+                                        format!("{} {}\n{}", revision.date, &revision.commit[..revision.commit.len().min(10)], revision.summary)
+                                    })
+                                })
+                                .show(ui, &mut self.plot_exports);
+                            egui::ScrollArea::both().id_source("timeline_table").show(ui, |ui| {
+                                TableBuilder::new(ui)
+                                    .striped(true)
+                                    .resizable(true)
+                                    .column(Column::initial(90.0).resizable(true))
+                                    .column(Column::initial(80.0).resizable(true))
+                                    .column(Column::initial(200.0).resizable(true))
+                                    .column(Column::initial(100.0).resizable(true))
+                                    .column(Column::remainder().resizable(true))
+                                    .header(20.0, |mut header| {
+                                        for title in ["Date", "Commit", "Raw", "Value", "Summary"] {
+                                            header.col(|ui| {
+                                                ui.heading(title);
+                                            });
+                                        }
+                                    })
+                                    .body(|mut body| {
+                                        for point in &timeline.points {
+                                            let revision = &history.revisions[point.revision];
+                                            body.row(20.0, |mut row| {
+                                                row.col(|ui| {
+                                                    ui.label(&revision.date);
+                                                });
+                                                row.col(|ui| {
+                                                    ui.label(&revision.commit[..revision.commit.len().min(10)]).on_hover_text(&revision.commit);
+                                                });
+                                                row.col(|ui| {
+                                                    ui.label(point.raw.as_deref().unwrap_or("(removed)"));
+                                                });
+                                                row.col(|ui| {
+                                                    ui.label(point.value.as_deref().unwrap_or(""));
+                                                });
+                                                row.col(|ui| {
+                                                    ui.label(&revision.summary);
+                                                });
+                                            });
+                                        }
+                                    });
+                            });
+                        });
+                    }
+                }
+            }
+        });
+
+        self.persist_settings(ctx);
+    }
+}
@@ -0,0 +1,269 @@
+use atmos::export::csv_escape;
+use egui::{Color32, Context, Event, Rect, Stroke, TextEdit, Ui, ViewportCommand};
+use egui_plot::{Legend, Line, Plot, Polygon};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+pub struct Series {
+    pub name: String,
+    pub color: Option<Color32>,
+    pub points: Vec<[f64; 2]>,
+}
+
+impl Series {
+    pub fn new(name: impl Into<String>, points: Vec<[f64; 2]>) -> Self {
+        Self {
+            name: name.into(),
+            color: None,
+            points,
+        }
+    }
+
+    pub fn color(mut self, color: Option<Color32>) -> Self {
+        self.color = color;
+        self
+    }
+
+    // Holds each value until the next point, for values that change at discrete revisions.
+    pub fn steps(mut self, end: f64) -> Self {
+        let mut points = Vec::new();
+        for (i, point) in self.points.iter().enumerate() {
+            if let Some(previous) = i.checked_sub(1).map(|i| self.points[i]) {
+                points.push([point[0], previous[1]]);
+            }
+            points.push(*point);
+        }
+        if let Some(last) = points.last().copied() {
+            if last[0] < end {
+                points.push([end, last[1]]);
+            }
+        }
+        self.points = points;
+        self
+    }
+}
+
+pub struct Chart<'a> {
+    id: &'a str,
+    x_label: &'a str,
+    y_label: &'a str,
+    series: Vec<Series>,
+    x_text: Box<dyn Fn(f64) -> String + 'a>,
+}
+
+impl<'a> Chart<'a> {
+    pub fn new(id: &'a str, x_label: &'a str, y_label: &'a str, series: Vec<Series>) -> Self {
+        Self {
+            id,
+            x_label,
+            y_label,
+            series,
+            x_text: Box::new(|x| format!("{}", x)),
+        }
+    }
+
+    pub fn x_text(mut self, x_text: impl Fn(f64) -> String + 'a) -> Self {
+        self.x_text = Box::new(x_text);
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui, exports: &mut PlotExports) {
+        if self.series.iter().all(|series| series.points.is_empty()) {
+            return;
+        }
+        let response = Plot::new(self.id)
+            .height(180.0)
+            .legend(Legend::default())
+            .x_axis_label(self.x_label)
+            .y_axis_label(self.y_label)
+            .label_formatter(|name, point| {
+                let x = (self.x_text)(point.x);
+                if name.is_empty() {
+                    format!("{}\n{:.4}", x, point.y)
+                } else {
+                    format!("{}\n{}: {:.4}", x, name, point.y)
+                }
+            })
+            .show(ui, |plot| {
+                for series in &self.series {
+                    let mut line = Line::new(series.points.clone()).name(&series.name);
+                    if let Some(color) = series.color {
+                        line = line.color(color);
+                    }
+                    plot.line(line);
+                }
+            })
+            .response;
+        exports.controls(ui, self.id, response.rect, || self.csv());
+    }
+
+    fn csv(&self) -> String {
+        let mut text = format!("series,{},{}\r\n", csv_escape(self.x_label), csv_escape(self.y_label));
+        for series in &self.series {
+            for [x, y] in &series.points {
+                text.push_str(&format!("{},{},{}\r\n", csv_escape(&series.name), x, y));
+            }
+        }
+        text
+    }
+}
+
+pub struct Heatmap<'a> {
+    id: &'a str,
+    x_label: &'a str,
+    y_label: &'a str,
+    value_label: &'a str,
+    x: &'a [f64],
+    y: &'a [f64],
+    cells: Vec<(Color32, String)>,
+    legend: Vec<(Color32, String)>,
+}
+
+impl<'a> Heatmap<'a> {
+    // Cells are stored row by row, one row per y value.
+    pub fn new(id: &'a str, labels: [&'a str; 3], x: &'a [f64], y: &'a [f64], cells: Vec<(Color32, String)>) -> Self {
+        let [x_label, y_label, value_label] = labels;
+        Self {
+            id,
+            x_label,
+            y_label,
+            value_label,
+            x,
+            y,
+            cells,
+            legend: Vec::new(),
+        }
+    }
+
+    pub fn legend(mut self, legend: Vec<(Color32, String)>) -> Self {
+        self.legend = legend;
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui, exports: &mut PlotExports) {
+        if self.x.is_empty() || self.y.is_empty() {
+            return;
+        }
+        let step = |values: &[f64]| if values.len() > 1 { values[1] - values[0] } else { 1.0 };
+        let (dx, dy) = (step(self.x), step(self.y));
+        let cell = |x: f64, y: f64| -> Option<&(Color32, String)> {
+            let (i, j) = (((x - self.x[0]) / dx).round(), ((y - self.y[0]) / dy).round());
+            if i < 0.0 || j < 0.0 || i as usize >= self.x.len() || j as usize >= self.y.len() {
+                return None;
+            }
+            self.cells.get(j as usize * self.x.len() + i as usize)
+        };
+        let response = Plot::new(self.id)
+            .height(320.0)
+            .x_axis_label(self.x_label)
+            .y_axis_label(self.y_label)
+            .label_formatter(|_, point| match cell(point.x, point.y) {
+                Some((_, text)) => format!("{}: {:.4}\n{}: {:.4}\n{}: {}", self.x_label, point.x, self.y_label, point.y, self.value_label, text),
+                None => format!("{}: {:.4}\n{}: {:.4}", self.x_label, point.x, self.y_label, point.y),
+            })
+            .show(ui, |plot| {
+                for (j, y) in self.y.iter().enumerate() {
+                    for (i, x) in self.x.iter().enumerate() {
+                        let Some((color, _)) = self.cells.get(j * self.x.len() + i) else {
+                            continue;
+                        };
+                        let (left, right, bottom, top) = (x - dx.abs() / 2.0, x + dx.abs() / 2.0, y - dy.abs() / 2.0, y + dy.abs() / 2.0);
+                        let corners = vec![[left, bottom], [right, bottom], [right, top], [left, top]];
+                        plot.polygon(Polygon::new(corners).fill_color(*color).stroke(Stroke::NONE));
+                    }
+                }
+            })
+            .response;
+        if !self.legend.is_empty() {
+            ui.horizontal_wrapped(|ui| {
+                for (color, label) in &self.legend {
+                    ui.colored_label(*color, "■");
+                    ui.label(label);
+                }
+            });
+        }
+        exports.controls(ui, self.id, response.rect, || self.csv());
+    }
+
+    fn csv(&self) -> String {
+        let mut text = format!("{},{},{}\r\n", csv_escape(self.x_label), csv_escape(self.y_label), csv_escape(self.value_label));
+        for (j, y) in self.y.iter().enumerate() {
+            for (i, x) in self.x.iter().enumerate() {
+                if let Some((_, value)) = self.cells.get(j * self.x.len() + i) {
+                    text.push_str(&format!("{},{},{}\r\n", x, y, csv_escape(value)));
+                }
+            }
+        }
+        text
+    }
+}
+
+// Blue for the lowest values through green to red for the highest.
+pub fn gradient(t: f64) -> Color32 {
+    egui::ecolor::Hsva::new(0.66 * (1.0 - t.clamp(0.0, 1.0)) as f32, 0.8, 0.9, 1.0).into()
+}
+
+// Well separated hues for categories, by stepping around the colour wheel by the golden ratio.
+pub fn category(index: usize) -> Color32 {
+    egui::ecolor::Hsva::new((index as f32 * 0.618_034).fract(), 0.7, 0.9, 1.0).into()
+}
+
+#[derive(Default)]
+pub struct PlotExports {
+    paths: HashMap<String, String>,
+    status: HashMap<String, String>,
+    pending: HashMap<String, (Rect, PathBuf)>,
+}
+
+impl PlotExports {
+    fn controls(&mut self, ui: &mut Ui, id: &str, rect: Rect, csv: impl FnOnce() -> String) {
+        ui.horizontal(|ui| {
+            let path = self.paths.entry(id.to_string()).or_default();
+            ui.add(TextEdit::singleline(path).hint_text(id).desired_width(160.0));
+            let base = if path.trim().is_empty() { PathBuf::from(id) } else { PathBuf::from(path.trim()) };
+            if ui.button("Save CSV").clicked() {
+                let path = base.with_extension("csv");
+                let status = match std::fs::write(&path, csv()) {
+                    Ok(()) => format!("Saved to {}", path.display()),
+                    Err(err) => format!("Error writing {}: {}", path.display(), err),
+                };
+                self.status.insert(id.to_string(), status);
+            }
+            if ui.button("Save PNG").clicked() {
+                self.pending.insert(id.to_string(), (rect, base.with_extension("png")));
+                ui.ctx().send_viewport_cmd(ViewportCommand::Screenshot);
+            }
+            if let Some(status) = self.status.get(id) {
+                ui.weak(status);
+            }
+        });
+    }
+
+    // A screenshot covers the whole window, so one answers every export still waiting for it.
+    pub fn poll(&mut self, ctx: &Context) {
+        let screenshot = ctx.input(|input| {
+            input.events.iter().find_map(|event| match event {
+                Event::Screenshot { image, .. } => Some(image.clone()),
+                _ => None,
+            })
+        });
+        let Some(image) = screenshot else {
+            return;
+        };
+        let pixels_per_point = ctx.pixels_per_point();
+        for (id, (rect, path)) in self.pending.drain() {
+            let status = match save_png(&image.region(&rect, Some(pixels_per_point)), &path) {
+                Ok(()) => format!("Saved to {}", path.display()),
+                Err(err) => format!("Error writing {}: {}", path.display(), err),
+            };
+            self.status.insert(id, status);
+        }
+    }
+}
+
+fn save_png(image: &egui::ColorImage, path: &Path) -> Result<(), String> {
+    let [width, height] = image.size;
+    let pixels: Vec<u8> = image.pixels.iter().flat_map(|pixel| pixel.to_array()).collect();
+    let buffer = image::RgbaImage::from_raw(width as u32, height as u32, pixels).ok_or("the screenshot is empty")?;
+    buffer.save(path).map_err(|err| err.to_string())
+}
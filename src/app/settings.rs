@@ -0,0 +1,63 @@
+use super::{SortOrder, Tab};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+pub const DEFAULT_VARIABLE_SET: &str = "Default";
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct Settings {
+    pub(super) source: Option<String>,
+    pub(super) selected_tab: Tab,
+    pub(super) sort_order: SortOrder,
+    pub(super) search_query: String,
+    pub(super) export_format: Option<String>,
+    pub(super) variable_set: String,
+    pub(super) variable_sets: BTreeMap<String, HashMap<String, String>>,
+    pub(super) window_size: Option<[f32; 2]>,
+}
+
+impl Settings {
+    pub fn load() -> Self {
+        let Some(path) = settings_path() else {
+            return Self::default();
+        };
+        match fs::read_to_string(&path) {
+            Ok(text) => serde_yaml::from_str(&text).unwrap_or_else(|err| {
+                eprintln!("Error parsing {}: {}, using defaults", path.display(), err);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn native_options(&self) -> eframe::NativeOptions {
+        let mut options = eframe::NativeOptions::default();
+        if let Some(size) = self.window_size {
+            options.viewport = options.viewport.with_inner_size(size);
+        }
+        options
+    }
+
+    pub fn save(&self) {
+        let Some(path) = settings_path() else {
+            return;
+        };
+        let written = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(&path, serde_yaml::to_string(self).unwrap_or_default()));
+        if let Err(err) = written {
+            eprintln!("Error writing {}: {}", path.display(), err);
+        }
+    }
+}
+
+fn settings_path() -> Option<PathBuf> {
+    std::env::var_os("ATMOS_CONFIG_DIR")
+        .map(PathBuf::from)
+        .or_else(|| dirs::config_dir().map(|dir| dir.join("atmos")))
+        .map(|dir| dir.join("settings.yml"))
+}
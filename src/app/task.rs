@@ -0,0 +1,54 @@
+use egui::Context;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Dropping a task (cancelling it, or replacing it with a newer one) sets its cancelled flag, which long jobs
+// started with spawn_cancellable check so their thread stops instead of finishing unwanted work.
+pub struct Task<T> {
+    receiver: Receiver<T>,
+    started: Instant,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl<T: Send + 'static> Task<T> {
+    pub fn spawn(ctx: &Context, job: impl FnOnce() -> T + Send + 'static) -> Self {
+        Self::spawn_cancellable(ctx, move |_| job())
+    }
+
+    pub fn spawn_cancellable(ctx: &Context, job: impl FnOnce(&dyn Fn() -> bool) -> T + Send + 'static) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let ctx = ctx.clone();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let flag = cancelled.clone();
+        thread::spawn(move || {
+            let result = job(&|| flag.load(Ordering::Relaxed));
+            if sender.send(result).is_ok() {
+                ctx.request_repaint();
+            }
+        });
+        Self { receiver, started: Instant::now(), cancelled }
+    }
+
+    pub fn poll(task: &mut Option<Self>) -> Option<T> {
+        let result = match task.as_ref()?.receiver.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => None,
+        };
+        *task = None;
+        result
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+}
+
+impl<T> Drop for Task<T> {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
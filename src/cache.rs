@@ -0,0 +1,170 @@
+use crate::error::{Error, Result};
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CacheEntry {
+    pub url: String,
+    pub fetched_at: u64,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl CacheEntry {
+    pub fn fetched_at(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(self.fetched_at)
+    }
+}
+
+pub struct Fetched {
+    pub text: String,
+    pub fetched_at: SystemTime,
+    // Why the cached copy was served instead of a fresh one.
+    pub offline: Option<String>,
+}
+
+pub fn cache_dir() -> Option<PathBuf> {
+    std::env::var_os("ATMOS_CACHE_DIR")
+        .map(PathBuf::from)
+        .or_else(|| dirs::cache_dir().map(|dir| dir.join("atmos")))
+}
+
+pub fn fetch(url: &str) -> Result<Fetched> {
+    let cached = load(url);
+    let mut request = reqwest::blocking::Client::new().get(url);
+    if let Some((_, entry)) = &cached {
+        if let Some(etag) = &entry.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &entry.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+
+    let error = match request.send() {
+        Ok(response) if response.status() == StatusCode::NOT_MODIFIED && cached.is_some() => {
+            let (text, mut entry) = cached.unwrap();
+            entry.fetched_at = now();
+            let _ = store(&text, &entry);
+            return Ok(Fetched { text, fetched_at: entry.fetched_at(), offline: None });
+        }
+        Ok(response) if response.status().is_success() => {
+            let header = |name| response.headers().get(name).and_then(|value| value.to_str().ok()).map(str::to_string);
+            let entry = CacheEntry {
+                url: url.to_string(),
+                fetched_at: now(),
+                etag: header(ETAG),
+                last_modified: header(LAST_MODIFIED),
+            };
+            match response.text() {
+                Ok(text) => {
+                    let _ = store(&text, &entry);
+                    return Ok(Fetched { text, fetched_at: entry.fetched_at(), offline: None });
+                }
+                Err(source) => Error::Network { url: url.to_string(), source },
+            }
+        }
+        // An error status means the server answered, usually because the ref or path is wrong, so a cached
+        // copy of it would be stale data passed off as the requested one.
+        Ok(response) => return Err(Error::Http { url: url.to_string(), status: response.status() }),
+        Err(source) => Error::Network { url: url.to_string(), source },
+    };
+
+    match cached {
+        Some((text, entry)) => Ok(Fetched { text, fetched_at: entry.fetched_at(), offline: Some(error.to_string()) }),
+        None => Err(error),
+    }
+}
+
+pub fn load(url: &str) -> Option<(String, CacheEntry)> {
+    let (text_path, entry_path) = paths(url)?;
+    let entry: CacheEntry = serde_yaml::from_str(&fs::read_to_string(entry_path).ok()?).ok()?;
+    if entry.url != url {
+        return None;
+    }
+    let text = fs::read_to_string(text_path).ok()?;
+    Some((text, entry))
+}
+
+// The cache only saves requests, so callers are free to ignore a failed write.
+fn store(text: &str, entry: &CacheEntry) -> std::io::Result<()> {
+    let Some((text_path, entry_path)) = paths(&entry.url) else {
+        return Ok(());
+    };
+    if let Some(dir) = text_path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&text_path, text)?;
+    fs::write(&entry_path, serde_yaml::to_string(entry).unwrap_or_default())
+}
+
+fn paths(url: &str) -> Option<(PathBuf, PathBuf)> {
+    let key = key(url);
+    let dir = cache_dir()?;
+    Some((dir.join(&key), dir.join(format!("{}.meta.yml", key))))
+}
+
+// The readable part only helps when browsing the cache directory, the hash of the full URL is
+// what keeps URLs that sanitize to the same text apart.
+fn key(url: &str) -> String {
+    let readable: String = url
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' })
+        .collect();
+    let start = readable.char_indices().rev().nth(79).map_or(0, |(index, _)| index);
+    format!("{}-{:016x}", &readable[start..], fnv1a(url))
+}
+
+// FNV-1a rather than `DefaultHasher`, whose output may change between Rust releases and would
+// orphan the cache.
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3))
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs())
+}
+
+pub fn format_age(time: SystemTime) -> String {
+    let seconds = SystemTime::now().duration_since(time).map_or(0, |duration| duration.as_secs());
+    match seconds {
+        0..=59 => "just now".to_string(),
+        60..=3599 => format!("{} min ago", seconds / 60),
+        3600..=86399 => format!("{} h ago", seconds / 3600),
+        _ => format!("{} days ago", seconds / 86400),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distinct_urls_get_distinct_keys() {
+        let urls = [
+            "https://example.com/a/b.yml",
+            "https://example.com/a_b.yml",
+            "http://example.com/a/b.yml",
+            "https://example.com/a/b.yml?ref=main",
+            "https://example.com/a/b.yml?ref_main",
+        ];
+        for (i, a) in urls.iter().enumerate() {
+            for b in &urls[i + 1..] {
+                assert_ne!(key(a), key(b), "{} and {}", a, b);
+            }
+        }
+        assert_eq!(key(urls[0]), key(urls[0]));
+    }
+
+    #[test]
+    fn long_urls_keep_a_short_key() {
+        let url = format!("https://example.com/{}", "segment/".repeat(100));
+        assert!(key(&url).len() <= 97);
+    }
+}
@@ -0,0 +1,198 @@
+use crate::mixture::Mixture;
+use crate::simulation::Simulation;
+use std::collections::BTreeSet;
+
+// The design has settled once temperature and pressure stay within this fraction over this many ticks.
+const SETTLE_TICKS: usize = 50;
+const SETTLE_TOLERANCE: f64 = 1e-3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Feed {
+    pub gas: usize,
+    pub rate: f64,
+    pub temperature: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chamber {
+    pub volume: f64,
+    pub pressure: f64,
+    pub ignition_temperature: f64,
+    pub feeds: Vec<Feed>,
+    pub max_ticks: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChamberStep {
+    pub tick: usize,
+    pub temperature: f64,
+    pub pressure: f64,
+    pub energy_released: f64,
+    pub exhausted: Vec<f64>,
+    pub reactions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Flow {
+    pub gas: usize,
+    pub fed: f64,
+    pub exhausted: f64,
+}
+
+impl Flow {
+    // Positive for products, negative for consumed fuel.
+    pub fn net(&self) -> f64 {
+        self.exhausted - self.fed
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Design {
+    pub steps: Vec<ChamberStep>,
+    pub settled: Option<usize>,
+    pub temperature: f64,
+    pub pressure: f64,
+    pub energy_released: f64,
+    pub flows: Vec<Flow>,
+    pub reactions: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+impl Chamber {
+    pub fn fed(&self, gases: usize) -> Vec<f64> {
+        let mut fed = vec![0.0; gases];
+        for feed in &self.feeds {
+            if let Some(moles) = fed.get_mut(feed.gas) {
+                *moles += feed.rate;
+            }
+        }
+        fed
+    }
+
+    // The chamber starts filled with the feed mix at the target pressure, already at ignition temperature.
+    pub fn initial_mixture(&self, simulation: &Simulation) -> Mixture {
+        let gases = simulation.gases().len();
+        let mut mixture = Mixture::new(gases, self.ignition_temperature, self.volume);
+        let fed = self.fed(gases);
+        let total: f64 = fed.iter().sum();
+        if total > 0.0 {
+            let moles = simulation.physics().moles_for_pressure(self.pressure, self.ignition_temperature, self.volume);
+            mixture.moles = fed.iter().map(|rate| moles * rate / total).collect();
+        }
+        mixture
+    }
+
+    // Every tick the feeds are merged in, the reactions run, and an exhaust vents the mixture down to the target pressure.
+    pub fn design(&self, simulation: &Simulation) -> Result<Design, String> {
+        self.design_until(simulation, &|| false)
+    }
+
+    // Same as design, but gives up with an error as soon as `cancelled` returns true.
+    pub fn design_until(&self, simulation: &Simulation, cancelled: &dyn Fn() -> bool) -> Result<Design, String> {
+        let (gases, physics) = (simulation.gases(), simulation.physics());
+        let mut mixture = self.initial_mixture(simulation);
+        let mut warnings = BTreeSet::new();
+        let mut steps: Vec<ChamberStep> = Vec::new();
+        let mut settled = None;
+        for tick in 1..=self.max_ticks {
+            if cancelled() {
+                return Err("the design was cancelled".to_string());
+            }
+            for feed in &self.feeds {
+                let mut giver = Mixture::new(gases.len(), feed.temperature, self.volume);
+                *giver.moles.get_mut(feed.gas).ok_or_else(|| format!("feed gas #{} is not loaded", feed.gas))? = feed.rate;
+                mixture.merge(&giver, gases, physics);
+            }
+            let before = mixture.thermal_energy(gases, physics);
+            let reactions = simulation.tick(&mut mixture, &mut warnings);
+            let energy_released = mixture.thermal_energy(gases, physics) - before;
+
+            let pressure = mixture.pressure(physics);
+            let fraction = if pressure > self.pressure { 1.0 - self.pressure / pressure } else { 0.0 };
+            let exhausted: Vec<f64> = mixture.moles.iter().map(|moles| moles * fraction).collect();
+            for (moles, removed) in mixture.moles.iter_mut().zip(&exhausted) {
+                *moles -= removed;
+            }
+            steps.push(ChamberStep {
+                tick,
+                temperature: mixture.temperature,
+                pressure: mixture.pressure(physics),
+                energy_released,
+                exhausted,
+                reactions,
+            });
+            if steps.len() >= SETTLE_TICKS {
+                let window = &steps[steps.len() - SETTLE_TICKS..];
+                let steady = |value: fn(&ChamberStep) -> f64| {
+                    let (min, max) = window.iter().map(value).fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| (min.min(value), max.max(value)));
+                    max - min <= SETTLE_TOLERANCE * max.abs()
+                };
+                if steady(|step| step.temperature) && steady(|step| step.pressure) {
+                    settled = Some(tick);
+                    break;
+                }
+            }
+        }
+
+        let window = &steps[steps.len().saturating_sub(SETTLE_TICKS)..];
+        let average = |value: &dyn Fn(&ChamberStep) -> f64| if window.is_empty() { 0.0 } else { window.iter().map(value).sum::<f64>() / window.len() as f64 };
+        let fed = self.fed(gases.len());
+        let flows = (0..gases.len())
+            .map(|gas| Flow {
+                gas,
+                fed: fed[gas],
+                exhausted: average(&|step| step.exhausted[gas]),
+            })
+            .filter(|flow| flow.fed > 0.0 || flow.exhausted > 0.0)
+            .collect();
+        let mut reactions: Vec<String> = Vec::new();
+        for reaction in window.iter().flat_map(|step| &step.reactions) {
+            if !reactions.contains(reaction) {
+                reactions.push(reaction.clone());
+            }
+        }
+        Ok(Design {
+            temperature: average(&|step| step.temperature),
+            pressure: average(&|step| step.pressure),
+            energy_released: average(&|step| step.energy_released),
+            settled,
+            flows,
+            reactions,
+            warnings: warnings.into_iter().collect(),
+            steps,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gas::parse_gases;
+    use crate::mixture::Physics;
+    use std::collections::HashMap;
+
+    #[test]
+    fn unloaded_feed_gases_and_cancelled_runs_are_errors() {
+        let gases = parse_gases("- type: gas\n  id: 0\n  name: Oxygen\n  specificHeat: 20\n").unwrap();
+        let physics = Physics {
+            r: 8.314462618,
+            heat_scale: 1.0,
+            minimum_heat_capacity: 0.0003,
+            minimum_temperature_delta: 0.01,
+            room_temperature: 293.15,
+            cell_volume: 2500.0,
+        };
+        let simulation = Simulation::new(&gases, &[], &[], physics, &HashMap::new());
+        let chamber = Chamber {
+            volume: 2500.0,
+            pressure: 1000.0,
+            ignition_temperature: 1000.0,
+            feeds: vec![Feed { gas: 3, rate: 1.0, temperature: 293.15 }],
+            max_ticks: 10,
+        };
+        assert_eq!(chamber.design(&simulation), Err("feed gas #3 is not loaded".to_string()));
+        let chamber = Chamber { feeds: vec![Feed { gas: 0, rate: 1.0, temperature: 293.15 }], ..chamber };
+        assert!(chamber.design(&simulation).is_ok());
+        assert_eq!(chamber.design_until(&simulation, &|| true), Err("the design was cancelled".to_string()));
+    }
+}
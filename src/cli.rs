@@ -0,0 +1,917 @@
+use atmos::chamber::{Chamber, Feed};
+use atmos::constants::expressions;
+use atmos::diff::{compare, report, Snapshot};
+use atmos::eval::sweep_values;
+use atmos::export::{Format, Table};
+use atmos::mixture::{Mixture, Physics};
+use atmos::phase::{phase_map, Axis, Parameter};
+use atmos::reaction::requirement_warnings;
+use atmos::simulation::Simulation;
+use atmos::source::CONSTANTS_PATH;
+use atmos::teg::Generator;
+use atmos::variables::read_variables;
+use atmos::{fetch_and_parse_constants, fetch_and_parse_gases, fetch_and_parse_reactions, fetch_gas_names, fetch_generator, DataSource, DependencyGraph, Error, Evaluator, Gas, Origin};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage: atmos <command> [options]
+
+Commands:
+    constants list              List all constants
+    constants get <name>        Print the value of a single constant
+    constants info <name>       Print the type, value, description and source line of a constant
+    constants search <query>    List constants whose name or value contains the query
+    constants deps <name>       Show what a constant depends on and what depends on it
+    constants check             List circular and dangling constant references
+    gases                       List gas prototypes
+    reactions                   List reaction prototypes
+    eval <expression>           Evaluate an expression using the constants
+    mix <gas>=<amount>...       Show the properties of a gas mixture; amounts are moles, or partial
+                                pressures with a kPa suffix, and gases are matched by name,
+                                reagent or id
+    simulate <gas>=<amount>...  Step a mixture through the reactions tick by tick, with amounts as
+                                for mix
+    phase <x> <y> <gas>=<amount>...
+                                Simulate a mixture over a grid of two parameters and list the
+                                energy released and the reactions that fired; each axis is
+                                temperature=<from>..<to>, pressure=<from>..<to> (kPa) or
+                                <gas>:<gas>=<from>..<to> for the mole ratio of two gases
+    chamber <gas>=<rate>[@<kelvin>]...
+                                Estimate the steady state of a burn chamber fed with gases at the
+                                given moles per tick and temperatures (defaulting to T20C), vented
+                                down to --pressure and lit at --temperature
+    teg hot=<kelvin> <gas>=<amount>... cold=<kelvin> <gas>=<amount>...
+                                Estimate the heat transfer and electrical output of a
+                                thermo-electric generator from the gas passing its hot and cold
+                                circulators each update, with amounts as for mix
+    sweep <name>=<from>..<to> <expression>
+                                Evaluate an expression for evenly spaced values of a variable
+                                or constant
+    diff <old> <new>            Compare constants, gases and reactions between two sources
+    history <name>              Show how a constant, gas or reaction changed across the git
+                                history of the --local checkout
+    help                        Show this message
+
+Options:
+    --tsv                       Print tab-separated values instead of an aligned table
+    --format <format>           Print constants, gases or reactions as csv, json, markdown or wiki
+    --report                    Print diff results as a Markdown report
+    --limit <count>             Only walk the last <count> commits for history
+    --var <name>=<value>        Define a variable for eval (may be repeated)
+    --vars <file>               Load variables for eval from a YAML file of name: value pairs
+    --temperature <kelvin>      Temperature of the mixture (defaults to T20C), or the ignition
+                                temperature of a chamber (defaults to PlasmaUpperTemperature)
+    --volume <liters>           Volume of the mixture or chamber (defaults to CellVolume)
+    --pressure <kPa>            Pressure a chamber is vented down to (defaults to 1000)
+    --interval <seconds>        Seconds between atmos device updates, to turn TEG energy into power
+                                (defaults to 1)
+    --ticks <count>             Number of reaction ticks to simulate (defaults to 30), or the
+                                most a chamber may take to settle (defaults to 1000)
+    --steps <count>             Number of values for sweep and along each phase axis (defaults to 20)
+    --source <spec>             Read data from owner/repo[@branch|tag|commit] on GitHub, a raw base URL
+                                or a local checkout; @ref alone means the upstream repository
+    --local <path>              Read data from a local Space Station 14 checkout
+
+Exit codes:
+    1    the constant or gas does not exist, the expression failed to evaluate or check found problems
+    2    invalid arguments or data source
+    3    network error
+    4    HTTP error status
+    5    local file could not be read
+    6    Atmospherics.cs or a prototype file could not be parsed
+    7    the git history of the local checkout could not be read
+
+Run without a command to start the graphical interface.";
+
+struct Options {
+    source: DataSource,
+    tsv: bool,
+    format: Option<Format>,
+    report: bool,
+    limit: Option<usize>,
+    temperature: Option<f64>,
+    volume: Option<f64>,
+    pressure: Option<f64>,
+    interval: f64,
+    ticks: Option<usize>,
+    steps: usize,
+    variables: HashMap<String, String>,
+}
+
+pub fn run(args: &[String]) -> ExitCode {
+    let mut options = Options {
+        source: DataSource::default(),
+        tsv: false,
+        format: None,
+        report: false,
+        limit: None,
+        temperature: None,
+        volume: None,
+        pressure: None,
+        interval: 1.0,
+        ticks: None,
+        steps: 20,
+        variables: HashMap::new(),
+    };
+    let mut positional = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--tsv" => options.tsv = true,
+            "--report" => options.report = true,
+            "--vars" => match iter.next().map(|path| read_variables(Path::new(path))) {
+                Some(Ok(variables)) => options.variables.extend(variables),
+                Some(Err(err)) => return fetch_error(err),
+                None => return usage_error("--vars expects a file"),
+            },
+            "--format" => match iter.next().map(|format| Format::parse(format)) {
+                Some(Some(format)) => options.format = Some(format),
+                _ => return usage_error("--format expects csv, json, markdown or wiki"),
+            },
+            "--limit" => match iter.next().map(|limit| limit.parse()) {
+                Some(Ok(limit)) => options.limit = Some(limit),
+                _ => return usage_error("--limit expects a number of commits"),
+            },
+            "--temperature" => match iter.next().map(|temperature| temperature.parse()) {
+                Some(Ok(temperature)) => options.temperature = Some(temperature),
+                _ => return usage_error("--temperature expects a number of kelvin"),
+            },
+            "--volume" => match iter.next().map(|volume| volume.parse()) {
+                Some(Ok(volume)) => options.volume = Some(volume),
+                _ => return usage_error("--volume expects a number of liters"),
+            },
+            "--pressure" => match iter.next().map(|pressure| pressure.parse()) {
+                Some(Ok(pressure)) => options.pressure = Some(pressure),
+                _ => return usage_error("--pressure expects a number of kPa"),
+            },
+            "--interval" => match iter.next().map(|interval| interval.parse()) {
+                Some(Ok(interval)) if interval > 0.0 => options.interval = interval,
+                _ => return usage_error("--interval expects a number of seconds"),
+            },
+            "--ticks" => match iter.next().map(|ticks| ticks.parse()) {
+                Some(Ok(ticks)) => options.ticks = Some(ticks),
+                _ => return usage_error("--ticks expects a number of ticks"),
+            },
+            "--steps" => match iter.next().map(|steps| steps.parse()) {
+                Some(Ok(steps)) if steps > 0 => options.steps = steps,
+                _ => return usage_error("--steps expects a number of values"),
+            },
+            "--var" => match iter.next().and_then(|var| var.split_once('=')) {
+                Some((name, value)) => {
+                    options.variables.insert(name.trim().to_string(), value.trim().to_string());
+                }
+                None => return usage_error("--var expects <name>=<value>"),
+            },
+            "--source" => match iter.next().map(|spec| DataSource::parse(spec)) {
+                Some(Ok(source)) => options.source = source,
+                Some(Err(err)) => return usage_error(&err.to_string()),
+                None => return usage_error("--source expects a specification"),
+            },
+            "--local" => match iter.next().map(DataSource::local) {
+                Some(Ok(source)) => options.source = source,
+                Some(Err(err)) => return usage_error(&err.to_string()),
+                None => return usage_error("--local expects a path"),
+            },
+            _ => positional.push(arg.as_str()),
+        }
+    }
+
+    match positional.as_slice() {
+        ["constants", "list"] => list_constants(&options, None),
+        ["constants", "search", query] => list_constants(&options, Some(query)),
+        ["constants", "get", name] => get_constant(name, &options),
+        ["constants", "info", name] => constant_info(name, &options),
+        ["constants", "deps", name] => constant_dependencies(name, &options),
+        ["constants", "check"] => check_constants(&options),
+        ["gases"] => list_gases(&options),
+        ["reactions"] => list_reactions(&options),
+        ["diff", old, new] => diff(old, new, &options),
+        ["history", name] => history(name, &options),
+        ["eval", expression @ ..] if !expression.is_empty() => eval(&expression.join(" "), &options),
+        ["mix", amounts @ ..] if !amounts.is_empty() => mix(amounts, &options),
+        ["simulate", amounts @ ..] if !amounts.is_empty() => simulate(amounts, &options),
+        ["phase", x, y, amounts @ ..] if !amounts.is_empty() => phase(x, y, amounts, &options),
+        ["chamber", feeds @ ..] if !feeds.is_empty() => chamber(feeds, &options),
+        ["teg", loops @ ..] if !loops.is_empty() => teg(loops, &options),
+        ["sweep", range, expression @ ..] if !expression.is_empty() => sweep(range, &expression.join(" "), &options),
+        ["help"] | ["--help"] | ["-h"] => {
+            println!("{}", USAGE);
+            ExitCode::SUCCESS
+        }
+        _ => usage_error("unknown command"),
+    }
+}
+
+#[cfg(not(feature = "gui"))]
+pub fn no_interface() -> ExitCode {
+    usage_error("this build has no graphical interface, run it with a command")
+}
+
+fn usage_error(message: &str) -> ExitCode {
+    eprintln!("error: {}\n\n{}", message, USAGE);
+    ExitCode::from(2)
+}
+
+fn fetch_error(err: Error) -> ExitCode {
+    eprintln!("{}", err);
+    ExitCode::from(match err {
+        Error::Network { .. } => 3,
+        Error::Http { .. } => 4,
+        Error::Io { .. } => 5,
+        Error::Yaml { .. } | Error::CSharp { .. } => 6,
+        Error::Git { .. } => 7,
+        Error::InvalidSource(_) => 2,
+    })
+}
+
+fn list_constants(options: &Options, query: Option<&str>) -> ExitCode {
+    let (constants, origin) = match fetch_and_parse_constants(&options.source) {
+        Ok(loaded) => loaded,
+        Err(err) => return fetch_error(err),
+    };
+    let expressions = expressions(&constants);
+    let evaluated = Evaluator::new(&expressions, &options.variables).evaluate_constants();
+    let query = query.map(str::to_lowercase);
+    let mut constants: Vec<_> = constants
+        .into_iter()
+        .filter(|constant| match &query {
+            Some(query) => constant.name.to_lowercase().contains(query) || constant.initializer.to_lowercase().contains(query),
+            None => true,
+        })
+        .collect();
+    constants.sort_by(|a, b| a.name.cmp(&b.name));
+    if let Some(format) = options.format {
+        output(&Table::constants(&constants, &evaluated).render(format));
+        return ExitCode::SUCCESS;
+    }
+    let rows: Vec<Vec<String>> = constants
+        .into_iter()
+        .map(|constant| {
+            let value = match &evaluated[&constant.name] {
+                Ok(value) => value.clone(),
+                Err(err) => err.clone(),
+            };
+            vec![constant.name, constant.ty, constant.initializer, value, constant.references.join(", ")]
+        })
+        .collect();
+    print_table(&["Constant Name", "Type", "Value", "Evaluated", "Depends On"], &rows, &origin, options);
+    ExitCode::SUCCESS
+}
+
+fn get_constant(name: &str, options: &Options) -> ExitCode {
+    let (constants, _) = match fetch_and_parse_constants(&options.source) {
+        Ok(loaded) => loaded,
+        Err(err) => return fetch_error(err),
+    };
+    match constants.iter().find(|constant| constant.name == name) {
+        Some(constant) => {
+            output(&format!("{}\n", constant.initializer));
+            ExitCode::SUCCESS
+        }
+        None => {
+            eprintln!("error: no constant named {}", name);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn constant_info(name: &str, options: &Options) -> ExitCode {
+    let (constants, origin) = match fetch_and_parse_constants(&options.source) {
+        Ok(loaded) => loaded,
+        Err(err) => return fetch_error(err),
+    };
+    let Some(constant) = constants.iter().find(|constant| constant.name == name) else {
+        eprintln!("error: no constant named {}", name);
+        return ExitCode::FAILURE;
+    };
+    let location = origin
+        .source
+        .line_url(CONSTANTS_PATH, constant.line)
+        .unwrap_or_else(|| format!("{}:{}", CONSTANTS_PATH, constant.line));
+    let mut text = format!("{}.{}\n{} = {}\n", constant.class, constant.name, constant.ty, constant.initializer);
+    if !constant.references.is_empty() {
+        text.push_str(&format!("Depends on: {}\n", constant.references.join(", ")));
+    }
+    if let Some(description) = &constant.description {
+        text.push_str(&format!("\n{}\n", description));
+    }
+    text.push_str(&format!("\n{}\n", location));
+    output(&text);
+    ExitCode::SUCCESS
+}
+
+fn constant_dependencies(name: &str, options: &Options) -> ExitCode {
+    let (constants, _) = match fetch_and_parse_constants(&options.source) {
+        Ok(loaded) => loaded,
+        Err(err) => return fetch_error(err),
+    };
+    let graph = DependencyGraph::new(&constants);
+    if !graph.contains(name) {
+        eprintln!("error: no constant named {}", name);
+        return ExitCode::FAILURE;
+    }
+    let mut text = format!("{}\n", name);
+    dependency_tree(&graph, name, &mut vec![name.to_string()], &mut text);
+    let dependents = graph.all_dependents(name);
+    if !dependents.is_empty() {
+        text.push_str(&format!("\nUsed by: {}\n", dependents.into_iter().collect::<Vec<_>>().join(", ")));
+    }
+    output(&text);
+    ExitCode::SUCCESS
+}
+
+fn dependency_tree(graph: &DependencyGraph, name: &str, path: &mut Vec<String>, text: &mut String) {
+    for dependency in graph.dependencies(name) {
+        let indent = "    ".repeat(path.len());
+        if path.contains(dependency) {
+            text.push_str(&format!("{}{} (cycle)\n", indent, dependency));
+        } else if !graph.contains(dependency) {
+            text.push_str(&format!("{}{} (not found)\n", indent, dependency));
+        } else {
+            text.push_str(&format!("{}{}\n", indent, dependency));
+            path.push(dependency.clone());
+            dependency_tree(graph, dependency, path, text);
+            path.pop();
+        }
+    }
+}
+
+fn check_constants(options: &Options) -> ExitCode {
+    let (constants, _) = match fetch_and_parse_constants(&options.source) {
+        Ok(loaded) => loaded,
+        Err(err) => return fetch_error(err),
+    };
+    let graph = DependencyGraph::new(&constants);
+    let mut text = String::new();
+    for cycle in graph.cycles() {
+        text.push_str(&format!("cycle: {}\n", cycle.join(" -> ")));
+    }
+    for dangling in graph.dangling() {
+        text.push_str(&format!("dangling: {} references unknown {}\n", dangling.constant, dangling.reference));
+    }
+    output(&text);
+    if text.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn list_gases(options: &Options) -> ExitCode {
+    let (gases, origin) = match fetch_and_parse_gases(&options.source) {
+        Ok(loaded) => loaded,
+        Err(err) => return fetch_error(err),
+    };
+    print_export(&Table::gases(&gases), &origin, options);
+    ExitCode::SUCCESS
+}
+
+fn list_reactions(options: &Options) -> ExitCode {
+    let (reactions, origin) = match fetch_and_parse_reactions(&options.source) {
+        Ok(loaded) => loaded,
+        Err(err) => return fetch_error(err),
+    };
+    let gases = match fetch_gas_names(&options.source) {
+        Ok(gases) => gases,
+        Err(err) => return fetch_error(err),
+    };
+    for warning in requirement_warnings(&reactions, &gases) {
+        eprintln!("warning: {}", warning);
+    }
+    print_export(&Table::reactions(&reactions, &gases), &origin, options);
+    ExitCode::SUCCESS
+}
+
+fn eval(expression: &str, options: &Options) -> ExitCode {
+    let (constants, _) = match fetch_and_parse_constants(&options.source) {
+        Ok(loaded) => loaded,
+        Err(err) => return fetch_error(err),
+    };
+    let constants = expressions(&constants);
+    let evaluator = Evaluator::new(&constants, &options.variables);
+    let mut context = evaluator.create_evaluation_context();
+    match evaluator.resolve_expression(expression, &mut context) {
+        Ok(result) => {
+            output(&format!("{}\n", result));
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+struct MixtureInput {
+    gases: Vec<Gas>,
+    evaluated: HashMap<String, Result<String, String>>,
+    physics: Physics,
+    mixture: Mixture,
+    origin: Origin,
+}
+
+fn mixture_input(amounts: &[&str], options: &Options) -> Result<MixtureInput, ExitCode> {
+    let (constants, _) = fetch_and_parse_constants(&options.source).map_err(fetch_error)?;
+    let (gases, origin) = fetch_and_parse_gases(&options.source).map_err(fetch_error)?;
+    let expressions = expressions(&constants);
+    let evaluated = Evaluator::new(&expressions, &options.variables).evaluate_constants();
+    let physics = Physics::from_constants(&evaluated).map_err(|err| {
+        eprintln!("error: {}", err);
+        ExitCode::FAILURE
+    })?;
+
+    let temperature = options.temperature.unwrap_or(physics.room_temperature);
+    let volume = options.volume.unwrap_or(physics.cell_volume);
+    let mut mixture = Mixture::new(gases.len(), temperature, volume);
+    add_amounts(&mut mixture, amounts, &gases, &physics)?;
+    Ok(MixtureInput {
+        gases,
+        evaluated,
+        physics,
+        mixture,
+        origin,
+    })
+}
+
+fn add_amounts(mixture: &mut Mixture, amounts: &[&str], gases: &[Gas], physics: &Physics) -> Result<(), ExitCode> {
+    for amount in amounts {
+        let Some((name, value)) = amount.split_once('=') else {
+            return Err(usage_error("expected <gas>=<amount>"));
+        };
+        let index = find_gas(gases, name)?;
+        let value = value.trim();
+        let parsed = match value.strip_suffix("kPa") {
+            Some(pressure) => pressure.trim().parse().map(|pressure| physics.moles_for_pressure(pressure, mixture.temperature, mixture.volume)),
+            None => value.parse(),
+        };
+        match parsed {
+            Ok(moles) => mixture.moles[index] += moles,
+            Err(_) => return Err(usage_error(&format!("{} is not a number of moles or kPa", value))),
+        }
+    }
+    Ok(())
+}
+
+fn mix(amounts: &[&str], options: &Options) -> ExitCode {
+    let MixtureInput { gases, physics, mixture, origin, .. } = match mixture_input(amounts, options) {
+        Ok(input) => input,
+        Err(code) => return code,
+    };
+    let (temperature, volume) = (mixture.temperature, mixture.volume);
+    let properties = mixture.properties(&gases, &physics);
+    let rows: Vec<Vec<String>> = properties
+        .components
+        .iter()
+        .map(|component| {
+            vec![
+                component.gas.clone(),
+                format!("{:.4}", component.moles),
+                format!("{:.2}%", component.fraction * 100.0),
+                format!("{:.3}", component.partial_pressure),
+                format!("{:.3}", component.heat_capacity),
+                format!("{:.3}", component.mass),
+            ]
+        })
+        .collect();
+    print_table(&["Gas", "Moles", "Fraction", "Partial Pressure (kPa)", "Heat Capacity (J/K)", "Mass (g)"], &rows, &origin, options);
+    let optional = |value: Option<f64>| value.map_or("n/a".to_string(), |value| format!("{:.4}", value));
+    output(&format!(
+        "\nTemperature: {} K\nVolume: {} L\nTotal moles: {:.4}\nPressure: {:.3} kPa\nHeat capacity: {:.3} J/K\nThermal energy: {:.3} J\nMass: {:.3} g\nMolar mass: {} g/mol\nHeat capacity ratio: {}\n",
+        temperature,
+        volume,
+        properties.total_moles,
+        properties.pressure,
+        properties.heat_capacity,
+        properties.thermal_energy,
+        properties.mass,
+        optional(properties.molar_mass),
+        optional(properties.heat_capacity_ratio)
+    ));
+    ExitCode::SUCCESS
+}
+
+fn simulate(amounts: &[&str], options: &Options) -> ExitCode {
+    let MixtureInput { gases, evaluated, physics, mixture, origin } = match mixture_input(amounts, options) {
+        Ok(input) => input,
+        Err(code) => return code,
+    };
+    let loaded = fetch_and_parse_reactions(&options.source).and_then(|(reactions, _)| Ok((reactions, fetch_gas_names(&options.source)?)));
+    let (reactions, names) = match loaded {
+        Ok(loaded) => loaded,
+        Err(err) => return fetch_error(err),
+    };
+    let simulation = Simulation::new(&gases, &names, &reactions, physics, &evaluated);
+    let run = simulation.run(mixture, options.ticks.unwrap_or(30));
+    for warning in &run.warnings {
+        eprintln!("warning: {}", warning);
+    }
+
+    let present: Vec<usize> = (0..gases.len()).filter(|&index| run.steps.iter().any(|step| step.moles[index] > 0.0)).collect();
+    let mut headers = vec!["Tick", "Temperature (K)", "Pressure (kPa)"];
+    headers.extend(present.iter().map(|&index| gases[index].name.as_str()));
+    headers.push("Reactions");
+    let rows: Vec<Vec<String>> = run
+        .steps
+        .iter()
+        .map(|step| {
+            let mut row = vec![step.tick.to_string(), format!("{:.3}", step.temperature), format!("{:.3}", step.pressure)];
+            row.extend(present.iter().map(|&index| format!("{:.4}", step.moles[index])));
+            row.push(step.reactions.join(", "));
+            row
+        })
+        .collect();
+    print_table(&headers, &rows, &origin, options);
+    ExitCode::SUCCESS
+}
+
+fn phase(x: &str, y: &str, amounts: &[&str], options: &Options) -> ExitCode {
+    let MixtureInput { gases, evaluated, physics, mixture, origin } = match mixture_input(amounts, options) {
+        Ok(input) => input,
+        Err(code) => return code,
+    };
+    let (x, y) = match (phase_axis(x, &gases, options), phase_axis(y, &gases, options)) {
+        (Ok(x), Ok(y)) => (x, y),
+        (Err(code), _) | (_, Err(code)) => return code,
+    };
+    let loaded = fetch_and_parse_reactions(&options.source).and_then(|(reactions, _)| Ok((reactions, fetch_gas_names(&options.source)?)));
+    let (reactions, names) = match loaded {
+        Ok(loaded) => loaded,
+        Err(err) => return fetch_error(err),
+    };
+    let simulation = Simulation::new(&gases, &names, &reactions, physics, &evaluated);
+    let map = phase_map(&simulation, &mixture, x, y, options.ticks.unwrap_or(30));
+    for warning in &map.warnings {
+        eprintln!("warning: {}", warning);
+    }
+
+    let (x_label, y_label) = (x.parameter.label(&gases), y.parameter.label(&gases));
+    let mut rows = Vec::new();
+    for (j, y_value) in map.y.iter().enumerate() {
+        for (i, x_value) in map.x.iter().enumerate() {
+            let Some(outcome) = map.get(i, j) else {
+                continue;
+            };
+            rows.push(vec![
+                x_value.to_string(),
+                y_value.to_string(),
+                format!("{:.3}", outcome.energy_released),
+                format!("{:.3}", outcome.temperature_change),
+                format!("{:.3}", outcome.final_pressure),
+                outcome.reactions.join(", "),
+            ]);
+        }
+    }
+    print_table(
+        &[&x_label, &y_label, "Energy Released (J)", "Temperature Change (K)", "Final Pressure (kPa)", "Reactions"],
+        &rows,
+        &origin,
+        options,
+    );
+    ExitCode::SUCCESS
+}
+
+fn phase_axis(spec: &str, gases: &[Gas], options: &Options) -> Result<Axis, ExitCode> {
+    let parsed = spec
+        .split_once('=')
+        .and_then(|(name, range)| Some((name.trim(), range.split_once("..")?)))
+        .and_then(|(name, (from, to))| Some((name, from.trim().parse::<f64>().ok()?, to.trim().parse::<f64>().ok()?)));
+    let Some((name, from, to)) = parsed else {
+        return Err(usage_error(&format!("{} is not <parameter>=<from>..<to>", spec)));
+    };
+    let gas = |name: &str| find_gas(gases, name);
+    let parameter = match name.to_lowercase().as_str() {
+        "temperature" => Parameter::Temperature,
+        "pressure" => Parameter::Pressure,
+        _ => match name.split_once(':') {
+            Some((first, second)) => Parameter::Ratio { gas: gas(first)?, other: gas(second)? },
+            None => return Err(usage_error(&format!("{} is not temperature, pressure or <gas>:<gas>", name))),
+        },
+    };
+    parameter.validate(gases).map_err(|err| usage_error(&err))?;
+    Ok(Axis { parameter, from, to, steps: options.steps })
+}
+
+fn find_gas(gases: &[Gas], name: &str) -> Result<usize, ExitCode> {
+    gases.iter().position(|gas| gas.matches(name.trim())).ok_or_else(|| {
+        eprintln!("error: no gas named {}", name.trim());
+        ExitCode::FAILURE
+    })
+}
+
+fn chamber(feeds: &[&str], options: &Options) -> ExitCode {
+    let MixtureInput { gases, evaluated, physics, mixture, origin } = match mixture_input(&[], options) {
+        Ok(input) => input,
+        Err(code) => return code,
+    };
+    let mut chamber = Chamber {
+        volume: mixture.volume,
+        pressure: options.pressure.unwrap_or(1000.0),
+        ignition_temperature: options
+            .temperature
+            .or_else(|| evaluated.get("PlasmaUpperTemperature")?.as_ref().ok()?.parse().ok())
+            .unwrap_or(1000.0),
+        feeds: Vec::new(),
+        max_ticks: options.ticks.unwrap_or(1000),
+    };
+    for feed in feeds {
+        let Some((name, value)) = feed.split_once('=') else {
+            return usage_error("expected <gas>=<rate>[@<kelvin>]");
+        };
+        let gas = match find_gas(&gases, name) {
+            Ok(gas) => gas,
+            Err(code) => return code,
+        };
+        let (rate, temperature) = match value.split_once('@') {
+            Some((rate, temperature)) => (rate.trim().parse(), temperature.trim().trim_end_matches('K').parse()),
+            None => (value.trim().parse(), Ok(physics.room_temperature)),
+        };
+        match (rate, temperature) {
+            (Ok(rate), Ok(temperature)) => chamber.feeds.push(Feed { gas, rate, temperature }),
+            _ => return usage_error(&format!("{} is not <rate>[@<kelvin>]", value)),
+        }
+    }
+    let loaded = fetch_and_parse_reactions(&options.source).and_then(|(reactions, _)| Ok((reactions, fetch_gas_names(&options.source)?)));
+    let (reactions, names) = match loaded {
+        Ok(loaded) => loaded,
+        Err(err) => return fetch_error(err),
+    };
+    let simulation = Simulation::new(&gases, &names, &reactions, physics, &evaluated);
+    let design = match chamber.design(&simulation) {
+        Ok(design) => design,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    for warning in &design.warnings {
+        eprintln!("warning: {}", warning);
+    }
+
+    let rows: Vec<Vec<String>> = design
+        .flows
+        .iter()
+        .map(|flow| vec![gases[flow.gas].name.clone(), format!("{:.4}", flow.fed), format!("{:.4}", flow.exhausted), format!("{:+.4}", flow.net())])
+        .collect();
+    print_table(&["Gas", "Fed (mol/tick)", "Exhausted (mol/tick)", "Net (mol/tick)"], &rows, &origin, options);
+    let settled = match design.settled {
+        Some(tick) => format!("settled after {} ticks", tick),
+        None => format!("still changing after {} ticks, values are averaged over the final ticks", design.steps.len()),
+    };
+    let reactions = if design.reactions.is_empty() { "none".to_string() } else { design.reactions.join(", ") };
+    output(&format!(
+        "\nState: {}\nTemperature: {:.3} K\nPressure: {:.3} kPa\nEnergy released: {:.3} J/tick\nReactions: {}\n",
+        settled, design.temperature, design.pressure, design.energy_released, reactions
+    ));
+    ExitCode::SUCCESS
+}
+
+fn teg(loops: &[&str], options: &Options) -> ExitCode {
+    let MixtureInput { gases, physics, mixture, origin, .. } = match mixture_input(&[], options) {
+        Ok(input) => input,
+        Err(code) => return code,
+    };
+    let mut loops_by_side: [Option<(f64, Vec<&str>)>; 2] = [None, None];
+    let mut current = None;
+    for arg in loops {
+        let side = match arg.split_once('=') {
+            Some(("hot", temperature)) => Some((0, temperature)),
+            Some(("cold", temperature)) => Some((1, temperature)),
+            _ => None,
+        };
+        match (side, current) {
+            (Some((side, temperature)), _) => {
+                let Ok(temperature) = temperature.trim().trim_end_matches('K').parse() else {
+                    return usage_error(&format!("{} is not a number of kelvin", temperature));
+                };
+                loops_by_side[side] = Some((temperature, Vec::new()));
+                current = Some(side);
+            }
+            (None, Some(side)) => {
+                if let Some((_, amounts)) = &mut loops_by_side[side] {
+                    amounts.push(arg);
+                }
+            }
+            (None, None) => return usage_error("teg expects hot=<kelvin> or cold=<kelvin> before the gases of each loop"),
+        }
+    }
+    let [Some(hot), Some(cold)] = loops_by_side else {
+        return usage_error("teg expects both a hot=<kelvin> and a cold=<kelvin> loop");
+    };
+    let mut sides = Vec::new();
+    for (temperature, amounts) in [hot, cold] {
+        let mut side = Mixture::new(gases.len(), temperature, mixture.volume);
+        if let Err(code) = add_amounts(&mut side, &amounts, &gases, &physics) {
+            return code;
+        }
+        sides.push(side);
+    }
+    let (generator, found) = match fetch_generator(&options.source) {
+        Ok((Some(generator), _)) => (generator, true),
+        Ok((None, _)) => (Generator::default(), false),
+        Err(err) => return fetch_error(err),
+    };
+
+    let rows: Vec<Vec<String>> = ["Hot", "Cold"]
+        .iter()
+        .zip(&sides)
+        .map(|(side, mixture)| {
+            vec![
+                side.to_string(),
+                format!("{:.3}", mixture.temperature),
+                format!("{:.3}", mixture.pressure(&physics)),
+                format!("{:.4}", mixture.total_moles()),
+                format!("{:.3}", mixture.heat_capacity(&gases, &physics)),
+            ]
+        })
+        .collect();
+    print_table(&["Loop", "Temperature (K)", "Pressure (kPa)", "Moles", "Heat Capacity (J/K)"], &rows, &origin, options);
+    output(&format!(
+        "\nThermal efficiency: {}{}\nPower factor: {}\n",
+        generator.thermal_efficiency,
+        if found { "" } else { " (no TEG prototype found, using the defaults)" },
+        generator.power_factor
+    ));
+    let Some(transfer) = generator.transfer(&sides[0], &sides[1], &gases, &physics) else {
+        output("No power: both loops need gas and the hot loop must be hotter than the cold loop\n");
+        return ExitCode::SUCCESS;
+    };
+    output(&format!(
+        "Heat transferred: {:.3} J\nCarnot efficiency: {:.2}%\nEfficiency: {:.2}%\nElectrical energy: {:.3} J per update\nPower: {:.3} W at {} s per update\nHot outlet: {:.3} K\nCold outlet: {:.3} K\n",
+        transfer.heat_transferred,
+        transfer.carnot_efficiency * 100.0,
+        transfer.efficiency * 100.0,
+        transfer.electrical_energy,
+        transfer.electrical_energy / options.interval,
+        options.interval,
+        transfer.hot_outlet_temperature,
+        transfer.cold_outlet_temperature
+    ));
+    ExitCode::SUCCESS
+}
+
+fn sweep(range: &str, expression: &str, options: &Options) -> ExitCode {
+    let parsed = range
+        .split_once('=')
+        .and_then(|(name, range)| Some((name.trim(), range.split_once("..")?)))
+        .and_then(|(name, (from, to))| Some((name, from.trim().parse::<f64>().ok()?, to.trim().parse::<f64>().ok()?)));
+    let Some((name, from, to)) = parsed else {
+        return usage_error("sweep expects <name>=<from>..<to>");
+    };
+    let (constants, origin) = match fetch_and_parse_constants(&options.source) {
+        Ok(loaded) => loaded,
+        Err(err) => return fetch_error(err),
+    };
+    let constants = expressions(&constants);
+    let values = sweep_values(from, to, options.steps);
+    let results = Evaluator::new(&constants, &options.variables).sweep(expression, name, &values);
+    let mut failed = false;
+    let rows: Vec<Vec<String>> = values
+        .iter()
+        .zip(results)
+        .map(|(value, result)| {
+            let result = result.map_or_else(
+                |err| {
+                    failed = true;
+                    err
+                },
+                |result| result.to_string(),
+            );
+            vec![value.to_string(), result]
+        })
+        .collect();
+    print_table(&[name, expression], &rows, &origin, options);
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn diff(old: &str, new: &str, options: &Options) -> ExitCode {
+    let (old, new) = match (DataSource::parse(old), DataSource::parse(new)) {
+        (Ok(old), Ok(new)) => (old, new),
+        (Err(err), _) | (_, Err(err)) => return usage_error(&err.to_string()),
+    };
+    let (old, new) = match (Snapshot::load(&old), Snapshot::load(&new)) {
+        (Ok(old), Ok(new)) => (old, new),
+        (Err(err), _) | (_, Err(err)) => return fetch_error(err),
+    };
+    let differences = compare(&old, &new);
+    if options.report {
+        output(&report(&differences, &old.origin, &new.origin));
+        return ExitCode::SUCCESS;
+    }
+    let rows: Vec<Vec<String>> = differences
+        .into_iter()
+        .map(|difference| {
+            vec![
+                difference.dataset.to_string(),
+                difference.name,
+                difference.field,
+                difference.change.to_string(),
+                difference.old.unwrap_or_default(),
+                difference.new.unwrap_or_default(),
+                difference.old_value.unwrap_or_default(),
+                difference.new_value.unwrap_or_default(),
+            ]
+        })
+        .collect();
+    if !options.tsv {
+        output(&format!("Old source: {}\n", old.origin));
+    }
+    print_table(&["Dataset", "Name", "Field", "Change", "Old", "New", "Old Value", "New Value"], &rows, &new.origin, options);
+    ExitCode::SUCCESS
+}
+
+fn history(name: &str, options: &Options) -> ExitCode {
+    let DataSource::Local(root) = &options.source else {
+        return usage_error("history needs a --local checkout");
+    };
+    let history = match atmos::history::history(root, options.limit) {
+        Ok(history) => history,
+        Err(err) => return fetch_error(err),
+    };
+    let mut rows = Vec::new();
+    for timeline in history.find(name) {
+        for point in &timeline.points {
+            let revision = &history.revisions[point.revision];
+            rows.push((point.revision, vec![
+                revision.date.clone(),
+                revision.commit.chars().take(10).collect(),
+                timeline.field.clone(),
+                point.raw.clone().unwrap_or_else(|| "(removed)".to_string()),
+                point.value.clone().unwrap_or_default(),
+                revision.summary.clone(),
+            ]));
+        }
+    }
+    if rows.is_empty() {
+        eprintln!("error: {} does not appear in the history of {}", name, root.display());
+        return ExitCode::FAILURE;
+    }
+    rows.sort_by_key(|(revision, _)| *revision);
+    let rows: Vec<Vec<String>> = rows.into_iter().map(|(_, row)| row).collect();
+    print_table(&["Date", "Commit", "Field", "Raw", "Value", "Summary"], &rows, &Origin::new(options.source.clone()), options);
+    ExitCode::SUCCESS
+}
+
+fn print_export(table: &Table, origin: &Origin, options: &Options) {
+    match options.format {
+        Some(format) => {
+            offline_warning(origin);
+            output(&table.render(format));
+        }
+        None => print_table(&table.headers(), &table.text_rows(), origin, options),
+    }
+}
+
+fn offline_warning(origin: &Origin) {
+    if let Some(reason) = &origin.offline {
+        eprintln!("warning: {}, using the cached copy", reason);
+    }
+}
+
+fn print_table(headers: &[&str], rows: &[Vec<String>], origin: &Origin, options: &Options) {
+    offline_warning(origin);
+    let mut text = String::new();
+    if options.tsv {
+        text.push_str(&format!("{}\n", headers.join("\t")));
+        for row in rows {
+            text.push_str(&format!("{}\n", row.join("\t")));
+        }
+        output(&text);
+        return;
+    }
+
+    let mut widths: Vec<usize> = headers.iter().map(|header| header.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let format_row = |cells: &[String]| {
+        let line = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        format!("{}\n", line.trim_end())
+    };
+    let headers: Vec<String> = headers.iter().map(|header| header.to_string()).collect();
+    let separator: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+    text.push_str(&format!("Source: {}\n", origin));
+    text.push_str(&format_row(&headers));
+    text.push_str(&format_row(&separator));
+    for row in rows {
+        text.push_str(&format_row(row));
+    }
+    output(&text);
+}
+
+fn output(text: &str) {
+    // A closed pipe (e.g. `atmos constants list | head`) is not an error worth reporting.
+    let _ = std::io::stdout().lock().write_all(text.as_bytes());
+}
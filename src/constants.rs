@@ -0,0 +1,347 @@
+use crate::csharp::{doc_summary, is_primitive_type, tokenize, Expr, ParseError, Parser, Spanned, Token};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constant {
+    pub name: String,
+    pub ty: String,
+    pub readonly: bool,
+    pub class: String,
+    pub initializer: String,
+    pub expr: Expr,
+    pub references: Vec<String>,
+    pub description: Option<String>,
+    pub line: usize,
+}
+
+impl Constant {
+    pub fn expression(&self) -> String {
+        match (&self.expr, self.ty.as_str()) {
+            (Expr::Number { value, .. }, "float" | "double" | "decimal") => format!("{:?}", value),
+            (expr, _) => expr.to_evalexpr(),
+        }
+    }
+}
+
+pub fn expressions(constants: &[Constant]) -> HashMap<String, String> {
+    constants
+        .iter()
+        .map(|constant| (constant.name.clone(), constant.expression()))
+        .collect()
+}
+
+enum Scope {
+    Type(String),
+    Enum,
+    Namespace,
+}
+
+pub fn parse_constants(text: &str) -> Result<Vec<Constant>, ParseError> {
+    let tokens = tokenize(text)?;
+    let chars: Vec<char> = text.chars().collect();
+    let mut constants = Vec::new();
+    let mut scopes: Vec<Scope> = Vec::new();
+    let mut statement_start = 0;
+    let mut nesting = 0;
+    let mut i = 0;
+
+    while i < tokens.len() {
+        match tokens[i].token {
+            Token::Punct("(" | "[") => nesting += 1,
+            Token::Punct(")" | "]") => nesting -= 1,
+            Token::Punct("{") if nesting == 0 => {
+                let header = &tokens[statement_start..i];
+                if has_initializer(header) {
+                    // An array or object initializer; the statement goes on to its ';'.
+                    i = matching_brace(&tokens, i);
+                } else if let Some((keyword, name)) = type_declaration(header) {
+                    scopes.push(if keyword == "enum" { Scope::Enum } else { Scope::Type(name) });
+                    statement_start = i + 1;
+                } else if header.iter().any(|spanned| spanned.token == Token::Ident("namespace".to_string())) {
+                    scopes.push(Scope::Namespace);
+                    statement_start = i + 1;
+                } else {
+                    i = matching_brace(&tokens, i);
+                    statement_start = i + 1;
+                }
+            }
+            Token::Punct("}") if nesting == 0 => {
+                scopes.pop();
+                statement_start = i + 1;
+            }
+            Token::Punct(";") if nesting == 0 => {
+                if let Some(Scope::Type(_)) = scopes.last() {
+                    let class = scopes
+                        .iter()
+                        .filter_map(|scope| match scope {
+                            Scope::Type(name) => Some(name.as_str()),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>()
+                        .join(".");
+                    declaration(&tokens[statement_start..i], &chars, &class, &mut constants);
+                }
+                statement_start = i + 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    Ok(constants)
+}
+
+pub fn parse_enum(text: &str, name: &str) -> Result<Vec<String>, ParseError> {
+    let tokens = tokenize(text)?;
+    let Some(start) = tokens.windows(2).position(|pair| pair[0].token == Token::Ident("enum".to_string()) && pair[1].token == Token::Ident(name.to_string())) else {
+        return Ok(Vec::new());
+    };
+    let Some(open) = tokens[start..].iter().position(|spanned| spanned.token == Token::Punct("{")).map(|open| start + open) else {
+        return Ok(Vec::new());
+    };
+
+    let mut members: Vec<(i64, String)> = Vec::new();
+    let mut parser = Parser::new(&tokens[open + 1..matching_brace(&tokens, open).min(tokens.len())]);
+    while !parser.is_done() {
+        while parser.eat("[") {
+            while !parser.is_done() && !parser.eat("]") {
+                parser.advance();
+            }
+        }
+        let Some(Token::Ident(member)) = parser.advance() else {
+            return Err(parser.error(format!("expected a member of enum {}", name)));
+        };
+        let mut value = members.last().map_or(0, |(value, _)| value + 1);
+        if parser.eat("=") {
+            if let Expr::Number { value: number, .. } = parser.expression()? {
+                value = number as i64;
+            }
+        }
+        members.push((value, member.clone()));
+        if !parser.eat(",") && !parser.is_done() {
+            return Err(parser.error(format!("expected ',' after enum member {}", member)));
+        }
+    }
+    members.sort_by_key(|(value, _)| *value);
+    Ok(members.into_iter().map(|(_, member)| member).collect())
+}
+
+fn matching_brace(tokens: &[Spanned], open: usize) -> usize {
+    let mut depth = 0;
+    for (i, spanned) in tokens.iter().enumerate().skip(open) {
+        match spanned.token {
+            Token::Punct("{") => depth += 1,
+            Token::Punct("}") => {
+                depth -= 1;
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => {}
+        }
+    }
+    tokens.len()
+}
+
+// Only an '=' outside parentheses starts an initializer; one inside is a default parameter value.
+fn has_initializer(header: &[Spanned]) -> bool {
+    let mut depth = 0;
+    header.iter().any(|spanned| {
+        match spanned.token {
+            Token::Punct("(" | "[") => depth += 1,
+            Token::Punct(")" | "]") => depth -= 1,
+            _ => {}
+        }
+        depth == 0 && spanned.token == Token::Punct("=")
+    })
+}
+
+fn type_declaration(header: &[Spanned]) -> Option<(&str, String)> {
+    header.windows(2).find_map(|pair| match (&pair[0].token, &pair[1].token) {
+        (Token::Ident(keyword), Token::Ident(name)) if matches!(keyword.as_str(), "class" | "struct" | "record" | "interface" | "enum") => {
+            Some((keyword.as_str(), name.clone()))
+        }
+        _ => None,
+    })
+}
+
+fn declaration(statement: &[Spanned], chars: &[char], class: &str, constants: &mut Vec<Constant>) {
+    let Some(assign) = statement.iter().position(|spanned| spanned.token == Token::Punct("=")) else {
+        return;
+    };
+    let prefix = &statement[..assign];
+    let (Some(Token::Ident(ty)), Some(Token::Ident(name))) = (prefix.len().checked_sub(2).map(|i| &prefix[i].token), prefix.last().map(|spanned| &spanned.token)) else {
+        return;
+    };
+    let description = statement[0].doc.as_deref().map(doc_summary).filter(|summary| !summary.is_empty());
+    let modifiers: Vec<&str> = prefix[..prefix.len() - 2]
+        .iter()
+        .filter_map(|spanned| match &spanned.token {
+            Token::Ident(modifier) => Some(modifier.as_str()),
+            _ => None,
+        })
+        .collect();
+    let readonly = if modifiers.contains(&"const") {
+        false
+    } else if modifiers.contains(&"static") && modifiers.contains(&"readonly") {
+        true
+    } else {
+        return;
+    };
+    if !is_primitive_type(ty) {
+        return;
+    }
+
+    let mut name = name.clone();
+    let mut line = prefix[prefix.len() - 1].line;
+    let mut parser = Parser::new(&statement[assign + 1..]);
+    loop {
+        let start = parser.position();
+        // Initializers that aren't constant expressions (interpolated strings, object creation, ...) are skipped
+        // so one of them doesn't hide every other constant.
+        let Ok(expr) = parser.expression() else {
+            return;
+        };
+        if !parser.is_done() && parser.peek() != Some(&Token::Punct(",")) {
+            return;
+        }
+        let initializer = &statement[assign + 1 + start..assign + 1 + parser.position()];
+        constants.push(Constant {
+            name,
+            ty: ty.clone(),
+            readonly,
+            class: class.to_string(),
+            initializer: source_text(initializer, chars),
+            references: expr.references(),
+            expr,
+            description: description.clone(),
+            line,
+        });
+
+        if !parser.eat(",") {
+            return;
+        }
+        line = parser.line();
+        match (parser.advance(), parser.eat("=")) {
+            (Some(Token::Ident(next)), true) => name = next.clone(),
+            _ => return,
+        }
+    }
+}
+
+fn source_text(tokens: &[Spanned], chars: &[char]) -> String {
+    match (tokens.first(), tokens.last()) {
+        (Some(first), Some(last)) => chars[first.start..last.end]
+            .iter()
+            .collect::<String>()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" "),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(text: &str) -> Vec<String> {
+        parse_constants(text).unwrap().into_iter().map(|constant| constant.name).collect()
+    }
+
+    #[test]
+    fn reads_declarations_with_their_class_docs_and_lines() {
+        let text = "namespace Content.Shared.Atmos
+{
+    public static class Atmospherics
+    {
+        /// <summary>
+        ///     The gas constant.
+        /// </summary>
+        public const float R = 8.314462618f;
+        public static readonly float A = 1f, B = A * 2;
+        public static float NotConstant = 3f;
+        public readonly float NotStatic = 4f;
+        public const Gas NotPrimitive = Gas.Oxygen;
+        public static class Nested
+        {
+            internal const int Inner = 1;
+        }
+    }
+}";
+        let constants = parse_constants(text).unwrap();
+        let summary: Vec<(&str, &str, bool, &str, usize)> = constants
+            .iter()
+            .map(|constant| (constant.name.as_str(), constant.class.as_str(), constant.readonly, constant.initializer.as_str(), constant.line))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("R", "Atmospherics", false, "8.314462618f", 8),
+                ("A", "Atmospherics", true, "1f", 9),
+                ("B", "Atmospherics", true, "A * 2", 9),
+                ("Inner", "Atmospherics.Nested", false, "1", 15),
+            ]
+        );
+        assert_eq!(constants[0].description.as_deref(), Some("The gas constant."));
+        assert_eq!(constants[2].references, ["A"]);
+        assert_eq!(constants[0].expression(), "8.314462618");
+    }
+
+    #[test]
+    fn reads_enum_members_in_value_order() {
+        let text = "public enum Gas : sbyte { [Display] Oxygen = 1, Nitrogen = 0, Plasma, }";
+        assert_eq!(parse_enum(text, "Gas").unwrap(), ["Nitrogen", "Oxygen", "Plasma"]);
+        assert!(parse_enum(text, "Missing").unwrap().is_empty());
+    }
+
+    #[test]
+    fn method_with_default_parameter_does_not_swallow_next_declaration() {
+        let text = "
+            public static class Atmospherics
+            {
+                public const float Before = 1f;
+                public static void Helper(int a = 5) { }
+                public const float AfterMethod = 2f;
+            }";
+        assert_eq!(names(text), ["Before", "AfterMethod"]);
+    }
+
+    #[test]
+    fn unparsable_initializer_skips_only_that_constant() {
+        let text = "
+            public static class Atmospherics
+            {
+                public const float R = 8.314462618f;
+                public static readonly string Label = $\"R is {R}\";
+                public const float T0C = 273.15f;
+            }";
+        assert_eq!(names(text), ["R", "T0C"]);
+    }
+
+    #[test]
+    fn integer_division_truncates_like_csharp() {
+        let text = "
+            public static class Atmospherics
+            {
+                public const int Whole = 5;
+                public const int Half = Whole / 2;
+                public const float Exact = Whole / 2f;
+            }";
+        let constants = expressions(&parse_constants(text).unwrap());
+        let evaluated = crate::Evaluator::new(&constants, &HashMap::new()).evaluate_constants();
+        assert_eq!(evaluated["Half"], Ok("2".to_string()));
+        assert_eq!(evaluated["Exact"], Ok("2.5".to_string()));
+    }
+
+    #[test]
+    fn array_initializer_is_skipped_as_one_statement() {
+        let text = "
+            public static class Atmospherics
+            {
+                public static readonly float[] Table = { 1f, 2f };
+                public const int After = 3;
+            }";
+        assert_eq!(names(text), ["After"]);
+    }
+}
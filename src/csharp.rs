@@ -0,0 +1,709 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    Ident(String),
+    Number(String),
+    Str(String),
+    Char(String),
+    Punct(&'static str),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned {
+    pub token: Token,
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+    pub doc: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+const PUNCTUATION: &[&str] = &[
+    "<<=", ">>=", "??=", "<<", ">>", "<=", ">=", "==", "!=", "&&", "||", "??", "=>", "++", "--", "+=", "-=", "*=", "/=",
+    "%=", "&=", "|=", "^=", "::", "+", "-", "*", "/", "%", "<", ">", "=", "!", "~", "&", "|", "^", "?", ":", ";", ",",
+    ".", "(", ")", "[", "]", "{", "}", "@", "$",
+];
+
+const PRIMITIVE_TYPES: &[&str] = &[
+    "float", "double", "decimal", "int", "uint", "long", "ulong", "short", "ushort", "byte", "sbyte", "bool", "string",
+    "char",
+];
+
+pub fn tokenize(source: &str) -> Result<Vec<Spanned>, ParseError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut line_start = true;
+    let mut doc: Option<String> = None;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if c == '\n' {
+            line += 1;
+            line_start = true;
+            i += 1;
+            continue;
+        }
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        if c == '#' && line_start {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            continue;
+        }
+        line_start = false;
+
+        let start = i;
+        let start_line = line;
+        let token = if c == '/' && next == Some('/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            let comment: String = chars[start..i].iter().collect();
+            if let Some(text) = comment.strip_prefix("///") {
+                let doc = doc.get_or_insert_with(String::new);
+                doc.push_str(text.trim());
+                doc.push('\n');
+            }
+            None
+        } else if c == '/' && next == Some('*') {
+            i += 2;
+            loop {
+                match chars.get(i) {
+                    Some('*') if chars.get(i + 1) == Some(&'/') => break,
+                    Some('\n') => line += 1,
+                    Some(_) => {}
+                    None => return Err(ParseError { line: start_line, message: "unterminated comment".to_string() }),
+                }
+                i += 1;
+            }
+            i += 2;
+            None
+        } else if c.is_alphabetic() || c == '_' {
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            Some(Token::Ident(chars[start..i].iter().collect()))
+        } else if c.is_ascii_digit() || (c == '.' && next.is_some_and(|n| n.is_ascii_digit())) {
+            let hex = c == '0' && matches!(next, Some('x' | 'X'));
+            while i < chars.len() {
+                let d = chars[i];
+                let exponent_sign = !hex && (d == '+' || d == '-') && matches!(chars[i - 1], 'e' | 'E');
+                let fraction = d == '.' && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit());
+                if d.is_ascii_alphanumeric() || d == '_' || fraction || exponent_sign {
+                    i += 1;
+                } else {
+                    break;
+                }
+            }
+            Some(Token::Number(chars[start..i].iter().collect()))
+        } else if c == '"' || c == '\'' {
+            let verbatim = matches!(tokens.last(), Some(Spanned { token: Token::Punct("@"), .. }));
+            let mut text = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    Some('"') if verbatim && c == '"' && chars.get(i + 1) == Some(&'"') => {
+                        text.push('"');
+                        i += 1;
+                    }
+                    Some('\\') if !verbatim => {
+                        i += 1;
+                        match chars.get(i) {
+                            Some('n') => text.push('\n'),
+                            Some('t') => text.push('\t'),
+                            Some('0') => text.push('\0'),
+                            Some(escaped) => text.push(*escaped),
+                            None => {}
+                        }
+                    }
+                    Some(ch) if *ch == c => break,
+                    Some('\n') if !verbatim => {
+                        return Err(ParseError { line: start_line, message: "unterminated string literal".to_string() })
+                    }
+                    Some(ch) => {
+                        if *ch == '\n' {
+                            line += 1;
+                        }
+                        text.push(*ch);
+                    }
+                    None => return Err(ParseError { line: start_line, message: "unterminated string literal".to_string() }),
+                }
+                i += 1;
+            }
+            i += 1;
+            Some(if c == '"' { Token::Str(text) } else { Token::Char(text) })
+        } else {
+            let rest: String = chars[i..chars.len().min(i + 3)].iter().collect();
+            match PUNCTUATION.iter().find(|punct| rest.starts_with(**punct)) {
+                Some(punct) => {
+                    i += punct.chars().count();
+                    Some(Token::Punct(punct))
+                }
+                None => return Err(ParseError { line, message: format!("unexpected character '{}'", c) }),
+            }
+        };
+
+        if let Some(token) = token {
+            tokens.push(Spanned { token, line: start_line, start, end: i, doc: doc.take() });
+        }
+    }
+
+    Ok(tokens)
+}
+
+pub fn doc_summary(doc: &str) -> String {
+    let section = match (doc.find("<summary>"), doc.find("</summary>")) {
+        (Some(start), Some(end)) if start < end => &doc[start + "<summary>".len()..end],
+        _ => doc,
+    };
+    let mut text = String::new();
+    let mut rest = section;
+    while let Some(open) = rest.find('<') {
+        text.push_str(&rest[..open]);
+        let Some(close) = rest[open..].find('>') else {
+            break;
+        };
+        let tag = &rest[open + 1..open + close];
+        if let Some(reference) = tag.split('"').nth(1).filter(|_| tag.starts_with("see") || tag.starts_with("paramref")) {
+            text.push_str(reference.rsplit('.').next().unwrap_or(reference));
+        }
+        rest = &rest[open + close + 1..];
+    }
+    text.push_str(rest);
+    text.replace("&lt;", "<").replace("&gt;", ">").replace("&amp;", "&").split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Plus,
+    Minus,
+    Not,
+    BitNot,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Or,
+    And,
+    BitOr,
+    BitXor,
+    BitAnd,
+    Eq,
+    Ne,
+    Lt,
+    Gt,
+    Le,
+    Ge,
+    Shl,
+    Shr,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl BinaryOp {
+    fn from_punct(punct: &str) -> Option<(Self, u8)> {
+        Some(match punct {
+            "||" => (BinaryOp::Or, 1),
+            "&&" => (BinaryOp::And, 2),
+            "|" => (BinaryOp::BitOr, 3),
+            "^" => (BinaryOp::BitXor, 4),
+            "&" => (BinaryOp::BitAnd, 5),
+            "==" => (BinaryOp::Eq, 6),
+            "!=" => (BinaryOp::Ne, 6),
+            "<" => (BinaryOp::Lt, 7),
+            ">" => (BinaryOp::Gt, 7),
+            "<=" => (BinaryOp::Le, 7),
+            ">=" => (BinaryOp::Ge, 7),
+            "<<" => (BinaryOp::Shl, 8),
+            ">>" => (BinaryOp::Shr, 8),
+            "+" => (BinaryOp::Add, 9),
+            "-" => (BinaryOp::Sub, 9),
+            "*" => (BinaryOp::Mul, 10),
+            "/" => (BinaryOp::Div, 10),
+            "%" => (BinaryOp::Rem, 10),
+            _ => return None,
+        })
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BinaryOp::Or => "||",
+            BinaryOp::And => "&&",
+            BinaryOp::BitOr => "|",
+            BinaryOp::BitXor => "^",
+            BinaryOp::BitAnd => "&",
+            BinaryOp::Eq => "==",
+            BinaryOp::Ne => "!=",
+            BinaryOp::Lt => "<",
+            BinaryOp::Gt => ">",
+            BinaryOp::Le => "<=",
+            BinaryOp::Ge => ">=",
+            BinaryOp::Shl => "<<",
+            BinaryOp::Shr => ">>",
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "/",
+            BinaryOp::Rem => "%",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Number { value: f64, float: bool },
+    Bool(bool),
+    Str(String),
+    Name(Vec<String>),
+    Unary { op: UnaryOp, operand: Box<Expr> },
+    Binary { op: BinaryOp, lhs: Box<Expr>, rhs: Box<Expr> },
+    Conditional { condition: Box<Expr>, then: Box<Expr>, otherwise: Box<Expr> },
+    Cast { ty: String, operand: Box<Expr> },
+    Call { function: Vec<String>, args: Vec<Expr> },
+}
+
+impl Expr {
+    pub fn references(&self) -> Vec<String> {
+        let mut references = Vec::new();
+        self.collect_references(&mut references);
+        references
+    }
+
+    fn collect_references(&self, references: &mut Vec<String>) {
+        match self {
+            Expr::Name(path) => {
+                if builtin_value(path).is_none() {
+                    if let Some(name) = path.last() {
+                        if !references.contains(name) {
+                            references.push(name.clone());
+                        }
+                    }
+                }
+            }
+            Expr::Unary { operand, .. } | Expr::Cast { operand, .. } => operand.collect_references(references),
+            Expr::Binary { lhs, rhs, .. } => {
+                lhs.collect_references(references);
+                rhs.collect_references(references);
+            }
+            Expr::Conditional { condition, then, otherwise } => {
+                condition.collect_references(references);
+                then.collect_references(references);
+                otherwise.collect_references(references);
+            }
+            Expr::Call { args, .. } => args.iter().for_each(|arg| arg.collect_references(references)),
+            Expr::Number { .. } | Expr::Bool(_) | Expr::Str(_) => {}
+        }
+    }
+
+    pub fn to_evalexpr(&self) -> String {
+        match self {
+            Expr::Number { value, float: true } => format!("{:?}", value),
+            Expr::Number { value, float: false } => format!("{}", value),
+            Expr::Bool(value) => value.to_string(),
+            Expr::Str(text) => format!("{:?}", text),
+            Expr::Name(path) => match builtin_value(path) {
+                Some(value) => value.to_string(),
+                None => path.last().cloned().unwrap_or_default(),
+            },
+            Expr::Unary { op, operand } => {
+                let operand = operand.to_evalexpr();
+                match op {
+                    UnaryOp::Plus => operand,
+                    UnaryOp::Minus => format!("-{}", operand),
+                    UnaryOp::Not => format!("!{}", operand),
+                    UnaryOp::BitNot => format!("bitnot({})", operand),
+                }
+            }
+            Expr::Binary { op, lhs, rhs } => {
+                let (lhs, rhs) = (lhs.to_evalexpr(), rhs.to_evalexpr());
+                match op {
+                    BinaryOp::BitOr => format!("bitor({}, {})", lhs, rhs),
+                    BinaryOp::BitXor => format!("bitxor({}, {})", lhs, rhs),
+                    BinaryOp::BitAnd => format!("bitand({}, {})", lhs, rhs),
+                    BinaryOp::Shl => format!("shl({}, {})", lhs, rhs),
+                    BinaryOp::Shr => format!("shr({}, {})", lhs, rhs),
+                    _ => format!("({} {} {})", lhs, op.as_str(), rhs),
+                }
+            }
+            Expr::Conditional { condition, then, otherwise } => {
+                format!("if({}, {}, {})", condition.to_evalexpr(), then.to_evalexpr(), otherwise.to_evalexpr())
+            }
+            Expr::Cast { ty, operand } => match (ty.as_str(), operand.as_ref()) {
+                ("float" | "double" | "decimal", Expr::Number { value, .. }) => format!("{:?}", value),
+                ("float" | "double" | "decimal", operand) => format!("({} * 1.0)", operand.to_evalexpr()),
+                ("bool" | "string" | "char", operand) => operand.to_evalexpr(),
+                (_, operand) => format!("int({})", operand.to_evalexpr()),
+            },
+            Expr::Call { function, args } => {
+                let args: Vec<String> = args.iter().map(Expr::to_evalexpr).collect();
+                let name = function.last().map(String::as_str).unwrap_or_default();
+                let mapped = match (function.first().map(String::as_str), name) {
+                    (Some("Math" | "MathF" | "MathHelper"), "Max") => "max",
+                    (Some("Math" | "MathF" | "MathHelper"), "Min") => "min",
+                    (Some("Math" | "MathF"), "Sqrt") => "math::sqrt",
+                    (Some("Math" | "MathF"), "Pow") => "math::pow",
+                    (Some("Math" | "MathF"), "Abs") => "math::abs",
+                    (Some("Math" | "MathF"), "Floor") => "floor",
+                    (Some("Math" | "MathF"), "Ceiling") => "ceil",
+                    (Some("Math" | "MathF"), "Round") => "round",
+                    (Some("Math" | "MathF"), "Exp") => "math::exp",
+                    (Some("Math" | "MathF"), "Log") => "math::ln",
+                    _ => name,
+                };
+                format!("{}({})", mapped, args.join(", "))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Number { value, float: true } => write!(f, "{}f", value),
+            Expr::Number { value, float: false } => write!(f, "{}", value),
+            Expr::Bool(value) => write!(f, "{}", value),
+            Expr::Str(text) => write!(f, "{:?}", text),
+            Expr::Name(path) => write!(f, "{}", path.join(".")),
+            Expr::Unary { op, operand } => {
+                let op = match op {
+                    UnaryOp::Plus => "+",
+                    UnaryOp::Minus => "-",
+                    UnaryOp::Not => "!",
+                    UnaryOp::BitNot => "~",
+                };
+                write!(f, "{}{}", op, operand)
+            }
+            Expr::Binary { op, lhs, rhs } => write!(f, "({} {} {})", lhs, op.as_str(), rhs),
+            Expr::Conditional { condition, then, otherwise } => write!(f, "({} ? {} : {})", condition, then, otherwise),
+            Expr::Cast { ty, operand } => write!(f, "({}){}", ty, operand),
+            Expr::Call { function, args } => {
+                let args: Vec<String> = args.iter().map(Expr::to_string).collect();
+                write!(f, "{}({})", function.join("."), args.join(", "))
+            }
+        }
+    }
+}
+
+fn builtin_value(path: &[String]) -> Option<&'static str> {
+    let path: Vec<&str> = path.iter().map(String::as_str).collect();
+    Some(match path.as_slice() {
+        ["float", "MaxValue"] => "3.4028234663852886e38",
+        ["float", "MinValue"] => "-3.4028234663852886e38",
+        ["float", "Epsilon"] => "1.401298464324817e-45",
+        ["double", "MaxValue"] => "1.7976931348623157e308",
+        ["double", "MinValue"] => "-1.7976931348623157e308",
+        ["double", "Epsilon"] => "5e-324",
+        ["int", "MaxValue"] => "2147483647",
+        ["int", "MinValue"] => "-2147483648",
+        ["long", "MaxValue"] => "9223372036854775807",
+        ["long", "MinValue"] => "-9223372036854775808",
+        ["MathF" | "Math", "PI"] => "3.141592653589793",
+        ["MathF" | "Math", "E"] => "2.718281828459045",
+        _ => return None,
+    })
+}
+
+pub fn is_primitive_type(name: &str) -> bool {
+    PRIMITIVE_TYPES.contains(&name)
+}
+
+pub struct Parser<'a> {
+    tokens: &'a [Spanned],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(tokens: &'a [Spanned]) -> Self {
+        Self { tokens, pos: 0 }
+    }
+
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.pos >= self.tokens.len()
+    }
+
+    pub fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.pos).map(|spanned| &spanned.token)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&'a Token> {
+        self.tokens.get(self.pos + offset).map(|spanned| &spanned.token)
+    }
+
+    pub fn line(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .or_else(|| self.tokens.last())
+            .map_or(0, |spanned| spanned.line)
+    }
+
+    pub fn advance(&mut self) -> Option<&'a Token> {
+        let token = self.peek();
+        if token.is_some() {
+            self.pos += 1;
+        }
+        token
+    }
+
+    pub fn eat(&mut self, punct: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Punct(p)) if *p == punct) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError { line: self.line(), message: message.into() }
+    }
+
+    fn expect(&mut self, punct: &str) -> Result<(), ParseError> {
+        if self.eat(punct) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected '{}', found {}", punct, describe(self.peek()))))
+        }
+    }
+
+    pub fn expression(&mut self) -> Result<Expr, ParseError> {
+        let condition = self.binary(1)?;
+        if self.eat("?") {
+            let then = self.expression()?;
+            self.expect(":")?;
+            let otherwise = self.expression()?;
+            return Ok(Expr::Conditional {
+                condition: Box::new(condition),
+                then: Box::new(then),
+                otherwise: Box::new(otherwise),
+            });
+        }
+        Ok(condition)
+    }
+
+    fn binary(&mut self, min_precedence: u8) -> Result<Expr, ParseError> {
+        let mut lhs = self.unary()?;
+        while let Some(Token::Punct(punct)) = self.peek() {
+            let Some((op, precedence)) = BinaryOp::from_punct(punct) else {
+                break;
+            };
+            if precedence < min_precedence {
+                break;
+            }
+            self.pos += 1;
+            let rhs = self.binary(precedence + 1)?;
+            lhs = Expr::Binary { op, lhs: Box::new(lhs), rhs: Box::new(rhs) };
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expr, ParseError> {
+        let op = match self.peek() {
+            Some(Token::Punct("+")) => Some(UnaryOp::Plus),
+            Some(Token::Punct("-")) => Some(UnaryOp::Minus),
+            Some(Token::Punct("!")) => Some(UnaryOp::Not),
+            Some(Token::Punct("~")) => Some(UnaryOp::BitNot),
+            _ => None,
+        };
+        if let Some(op) = op {
+            self.pos += 1;
+            let operand = self.unary()?;
+            return Ok(Expr::Unary { op, operand: Box::new(operand) });
+        }
+
+        if let (Some(Token::Punct("(")), Some(Token::Ident(ty)), Some(Token::Punct(")"))) = (self.peek(), self.peek_at(1), self.peek_at(2)) {
+            if is_primitive_type(ty) {
+                self.pos += 3;
+                let operand = self.unary()?;
+                return Ok(Expr::Cast { ty: ty.clone(), operand: Box::new(operand) });
+            }
+        }
+
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, ParseError> {
+        let line = self.line();
+        match self.advance() {
+            Some(Token::Number(text)) => parse_number(text).ok_or_else(|| ParseError { line, message: format!("invalid number '{}'", text) }),
+            Some(Token::Str(text)) => Ok(Expr::Str(text.clone())),
+            Some(Token::Char(text)) => Ok(Expr::Str(text.clone())),
+            Some(Token::Ident(name)) if name == "true" => Ok(Expr::Bool(true)),
+            Some(Token::Ident(name)) if name == "false" => Ok(Expr::Bool(false)),
+            Some(Token::Ident(name)) => {
+                let mut path = vec![name.clone()];
+                while self.peek() == Some(&Token::Punct(".")) {
+                    self.pos += 1;
+                    match self.advance() {
+                        Some(Token::Ident(member)) => path.push(member.clone()),
+                        other => return Err(ParseError { line, message: format!("expected member name, found {}", describe(other)) }),
+                    }
+                }
+                if self.eat("(") {
+                    let mut args = Vec::new();
+                    if !self.eat(")") {
+                        loop {
+                            args.push(self.expression()?);
+                            if self.eat(")") {
+                                break;
+                            }
+                            self.expect(",")?;
+                        }
+                    }
+                    return Ok(Expr::Call { function: path, args });
+                }
+                Ok(Expr::Name(path))
+            }
+            Some(Token::Punct("(")) => {
+                let expr = self.expression()?;
+                self.expect(")")?;
+                Ok(expr)
+            }
+            other => Err(ParseError { line, message: format!("expected an expression, found {}", describe(other)) }),
+        }
+    }
+}
+
+fn describe(token: Option<&Token>) -> String {
+    match token {
+        Some(Token::Ident(name)) => format!("'{}'", name),
+        Some(Token::Number(text)) => format!("'{}'", text),
+        Some(Token::Str(_)) => "a string".to_string(),
+        Some(Token::Char(_)) => "a character".to_string(),
+        Some(Token::Punct(punct)) => format!("'{}'", punct),
+        None => "end of input".to_string(),
+    }
+}
+
+fn parse_number(text: &str) -> Option<Expr> {
+    let text = text.replace('_', "");
+    let lower = text.to_lowercase();
+    if let Some(hex) = lower.strip_prefix("0x") {
+        let digits = hex.trim_end_matches(['u', 'l']);
+        return i64::from_str_radix(digits, 16).ok().map(|value| Expr::Number { value: value as f64, float: false });
+    }
+    let (digits, float) = if let Some(digits) = lower.strip_suffix(['f', 'd', 'm']) {
+        (digits, true)
+    } else {
+        let digits = lower.trim_end_matches(['u', 'l']);
+        (digits, digits.contains(['.', 'e']))
+    };
+    digits.parse::<f64>().ok().map(|value| Expr::Number { value, float })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(source: &str) -> Vec<Token> {
+        tokenize(source).unwrap().into_iter().map(|spanned| spanned.token).collect()
+    }
+
+    fn parse(source: &str) -> Expr {
+        let tokens = tokenize(source).unwrap();
+        let mut parser = Parser::new(&tokens);
+        let expr = parser.expression().unwrap();
+        assert!(parser.is_done(), "{} was not fully parsed", source);
+        expr
+    }
+
+    #[test]
+    fn tokenizes_numbers_strings_and_punctuation() {
+        assert_eq!(
+            tokens(r#"x = 1.5e-3f + 0xFF >> "a\"b" @"c""d" 'e';"#),
+            [
+                Token::Ident("x".to_string()),
+                Token::Punct("="),
+                Token::Number("1.5e-3f".to_string()),
+                Token::Punct("+"),
+                Token::Number("0xFF".to_string()),
+                Token::Punct(">>"),
+                Token::Str("a\"b".to_string()),
+                Token::Punct("@"),
+                Token::Str("c\"d".to_string()),
+                Token::Char("e".to_string()),
+                Token::Punct(";"),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_comments_and_directives_but_keeps_doc_comments() {
+        let source = "#region Gases\n/// <summary>\n/// Moles in a <see cref=\"Atmospherics.CellVolume\"/>.\n/// </summary>\n// plain\n/* block\n */ X";
+        let spanned = tokenize(source).unwrap();
+        assert_eq!(spanned.len(), 1);
+        assert_eq!(spanned[0].token, Token::Ident("X".to_string()));
+        assert_eq!(spanned[0].line, 7);
+        assert_eq!(doc_summary(spanned[0].doc.as_deref().unwrap()), "Moles in a CellVolume.");
+    }
+
+    #[test]
+    fn reports_unterminated_strings() {
+        assert_eq!(tokenize("x = \"abc\ny").unwrap_err().line, 1);
+    }
+
+    #[test]
+    fn respects_operator_precedence() {
+        assert_eq!(parse("1 + 2 * 3 << 1").to_string(), "((1 + (2 * 3)) << 1)");
+        assert_eq!(parse("a || b && c == d").to_string(), "(a || (b && (c == d)))");
+        assert_eq!(parse("-a * b").to_string(), "(-a * b)");
+    }
+
+    #[test]
+    fn parses_casts_conditionals_and_calls() {
+        assert_eq!(parse("(float) A / 2").to_string(), "((float)A / 2)");
+        assert_eq!(parse("A > 0 ? B : C").to_string(), "((A > 0) ? B : C)");
+        assert_eq!(parse("MathF.Max(A, 2f)").to_string(), "MathF.Max(A, 2f)");
+        assert_eq!(parse("(A + B) * C").references(), ["A", "B", "C"]);
+    }
+
+    #[test]
+    fn parses_number_suffixes() {
+        assert_eq!(parse("0x10"), Expr::Number { value: 16.0, float: false });
+        assert_eq!(parse("5f"), Expr::Number { value: 5.0, float: true });
+        assert_eq!(parse("5"), Expr::Number { value: 5.0, float: false });
+        assert_eq!(parse("5.5"), Expr::Number { value: 5.5, float: true });
+        assert_eq!(parse("1_000UL"), Expr::Number { value: 1000.0, float: false });
+    }
+
+    #[test]
+    fn converts_to_evalexpr() {
+        assert_eq!(parse("(int) A").to_evalexpr(), "int(A)");
+        assert_eq!(parse("(int) -1.5").to_evalexpr(), "int(-1.5)");
+        assert_eq!(parse("(float) 5").to_evalexpr(), "5.0");
+        assert_eq!(parse("A ? 1 : 2").to_evalexpr(), "if(A, 1, 2)");
+        assert_eq!(parse("MathF.Sqrt(A) | 1").to_evalexpr(), "bitor(math::sqrt(A), 1)");
+        assert_eq!(parse("float.MaxValue").to_evalexpr(), "3.4028234663852886e38");
+        assert!(parse("float.MaxValue").references().is_empty());
+    }
+
+    #[test]
+    fn reports_what_it_expected() {
+        let tokens = tokenize("$\"R is {R}\"").unwrap();
+        assert_eq!(Parser::new(&tokens).expression().unwrap_err().message, "expected an expression, found '$'");
+    }
+}
@@ -0,0 +1,269 @@
+use crate::constants::expressions;
+use crate::error::Result;
+use crate::reaction::Effect;
+use crate::{fetch_and_parse_constants, fetch_and_parse_gases, fetch_and_parse_reactions, Constant, DataSource, Evaluator, Gas, Origin, Reaction};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub constants: Vec<Constant>,
+    pub gases: Vec<Gas>,
+    pub reactions: Vec<Reaction>,
+    pub origin: Origin,
+}
+
+impl Snapshot {
+    pub fn load(source: &DataSource) -> Result<Self> {
+        let (constants, origin) = fetch_and_parse_constants(source)?;
+        let (gases, _) = fetch_and_parse_gases(source)?;
+        let (reactions, _) = fetch_and_parse_reactions(source)?;
+        Ok(Self { constants, gases, reactions, origin })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Dataset {
+    Constant,
+    Gas,
+    Reaction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Difference {
+    pub dataset: Dataset,
+    pub name: String,
+    pub field: String,
+    pub change: Change,
+    pub old: Option<String>,
+    pub new: Option<String>,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+pub fn compare(old: &Snapshot, new: &Snapshot) -> Vec<Difference> {
+    let mut differences = compare_constants(&old.constants, &new.constants);
+    differences.extend(compare_fields(
+        Dataset::Gas,
+        old.gases.iter().map(|gas| (gas.name.clone(), gas_fields(gas))).collect(),
+        new.gases.iter().map(|gas| (gas.name.clone(), gas_fields(gas))).collect(),
+    ));
+    differences.extend(compare_fields(
+        Dataset::Reaction,
+        old.reactions.iter().map(|reaction| (reaction.id.clone(), reaction_fields(reaction))).collect(),
+        new.reactions.iter().map(|reaction| (reaction.id.clone(), reaction_fields(reaction))).collect(),
+    ));
+    differences
+}
+
+fn compare_constants(old: &[Constant], new: &[Constant]) -> Vec<Difference> {
+    let old_values = evaluate(old);
+    let new_values = evaluate(new);
+    let old: BTreeMap<&str, &Constant> = old.iter().map(|constant| (constant.name.as_str(), constant)).collect();
+    let new: BTreeMap<&str, &Constant> = new.iter().map(|constant| (constant.name.as_str(), constant)).collect();
+
+    let mut names: Vec<&str> = old.keys().chain(new.keys()).copied().collect();
+    names.sort();
+    names.dedup();
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let (old, new) = (old.get(name), new.get(name));
+            let change = match (old, new) {
+                (None, Some(_)) => Change::Added,
+                (Some(_), None) => Change::Removed,
+                (Some(old), Some(new)) if old.initializer != new.initializer || old.ty != new.ty || old_values.get(name) != new_values.get(name) => {
+                    Change::Changed
+                }
+                _ => return None,
+            };
+            Some(Difference {
+                dataset: Dataset::Constant,
+                name: name.to_string(),
+                field: "value".to_string(),
+                change,
+                old: old.map(|constant| constant.initializer.clone()),
+                new: new.map(|constant| constant.initializer.clone()),
+                old_value: old.and_then(|_| old_values.get(name).cloned()),
+                new_value: new.and_then(|_| new_values.get(name).cloned()),
+            })
+        })
+        .collect()
+}
+
+fn evaluate(constants: &[Constant]) -> HashMap<String, String> {
+    let expressions = expressions(constants);
+    let variables = HashMap::new();
+    Evaluator::new(&expressions, &variables)
+        .evaluate_constants()
+        .into_iter()
+        .map(|(name, value)| (name, value.unwrap_or_else(|err| err)))
+        .collect()
+}
+
+fn compare_fields(dataset: Dataset, old: BTreeMap<String, Vec<(&'static str, String)>>, new: BTreeMap<String, Vec<(&'static str, String)>>) -> Vec<Difference> {
+    let mut names: Vec<&String> = old.keys().chain(new.keys()).collect();
+    names.sort();
+    names.dedup();
+    let mut differences = Vec::new();
+    for name in names {
+        let (old_fields, new_fields) = match (old.get(name), new.get(name)) {
+            (Some(old_fields), Some(new_fields)) => (old_fields, new_fields),
+            (old_fields, new_fields) => {
+                let change = if old_fields.is_some() { Change::Removed } else { Change::Added };
+                differences.push(whole(dataset, name, change, old_fields, new_fields));
+                continue;
+            }
+        };
+        let mut fields: Vec<&'static str> = old_fields.iter().map(|(field, _)| *field).collect();
+        fields.extend(new_fields.iter().map(|(field, _)| *field).filter(|field| !old_fields.iter().any(|(other, _)| other == field)));
+        for field in fields {
+            let value = |fields: &Vec<(&'static str, String)>| fields.iter().find(|(other, _)| *other == field).map(|(_, value)| value.clone());
+            let (old_value, new_value) = (value(old_fields), value(new_fields));
+            let change = match (&old_value, &new_value) {
+                (None, Some(_)) => Change::Added,
+                (Some(_), None) => Change::Removed,
+                (old_value, new_value) if old_value != new_value => Change::Changed,
+                _ => continue,
+            };
+            differences.push(Difference {
+                dataset,
+                name: name.clone(),
+                field: field.to_string(),
+                change,
+                old: old_value,
+                new: new_value,
+                old_value: None,
+                new_value: None,
+            });
+        }
+    }
+    differences
+}
+
+fn whole(dataset: Dataset, name: &str, change: Change, old: Option<&Vec<(&'static str, String)>>, new: Option<&Vec<(&'static str, String)>>) -> Difference {
+    let summary = |fields: &Vec<(&'static str, String)>| fields.iter().map(|(field, value)| format!("{}: {}", field, value)).collect::<Vec<_>>().join(", ");
+    Difference {
+        dataset,
+        name: name.to_string(),
+        field: String::new(),
+        change,
+        old: old.map(summary),
+        new: new.map(summary),
+        old_value: None,
+        new_value: None,
+    }
+}
+
+pub(crate) fn gas_fields(gas: &Gas) -> Vec<(&'static str, String)> {
+    let mut fields: Vec<(&'static str, String)> = gas.fields().into_iter().map(|(field, value)| (field, optional(value))).collect();
+    if !gas.other.is_empty() {
+        let other: Vec<String> = gas.other_fields().into_iter().map(|(key, value)| format!("{}: {}", key, value)).collect();
+        fields.push(("other", other.join(", ")));
+    }
+    fields
+}
+
+pub(crate) fn reaction_fields(reaction: &Reaction) -> Vec<(&'static str, String)> {
+    vec![
+        ("priority", optional(reaction.priority)),
+        ("minimumTemperature", optional(reaction.minimum_temperature)),
+        ("maximumTemperature", optional(reaction.maximum_temperature)),
+        ("minimumEnergy", optional(reaction.minimum_energy)),
+        ("minimumRequirements", format!("{:?}", reaction.minimum_requirements)),
+        ("effects", reaction.effects.iter().map(Effect::to_string).collect::<Vec<_>>().join("; ")),
+    ]
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map_or_else(|| "n/a".to_string(), |value| value.to_string())
+}
+
+pub fn report(differences: &[Difference], old: &Origin, new: &Origin) -> String {
+    let mut report = format!("# Atmos changes\n\nFrom {}\nTo {}\n", old, new);
+    for (dataset, title) in [(Dataset::Constant, "Constants"), (Dataset::Gas, "Gases"), (Dataset::Reaction, "Reactions")] {
+        let rows: Vec<&Difference> = differences.iter().filter(|difference| difference.dataset == dataset).collect();
+        report.push_str(&format!("\n## {}\n\n", title));
+        if rows.is_empty() {
+            report.push_str("No changes.\n");
+            continue;
+        }
+        report.push_str("| Name | Field | Change | Old | New | Old value | New value |\n|---|---|---|---|---|---|---|\n");
+        for row in rows {
+            let cell = |value: &Option<String>| value.as_deref().unwrap_or("").replace('|', "\\|").replace('\n', " ");
+            report.push_str(&format!(
+                "| {} | {} | {} | {} | {} | {} | {} |\n",
+                row.name,
+                row.field,
+                row.change,
+                cell(&row.old),
+                cell(&row.new),
+                cell(&row.old_value),
+                cell(&row.new_value)
+            ));
+        }
+    }
+    report
+}
+
+impl fmt::Display for Dataset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Dataset::Constant => "constant",
+            Dataset::Gas => "gas",
+            Dataset::Reaction => "reaction",
+        })
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Change::Added => "added",
+            Change::Removed => "removed",
+            Change::Changed => "changed",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(fields: &[(&'static str, &str)]) -> Vec<(&'static str, String)> {
+        fields.iter().map(|(field, value)| (*field, value.to_string())).collect()
+    }
+
+    #[test]
+    fn compares_fields_from_both_sides() {
+        let old = BTreeMap::from([
+            ("Kept".to_string(), fields(&[("heat", "10"), ("color", "FF0000"), ("dropped", "1")])),
+            ("Gone".to_string(), fields(&[("heat", "1")])),
+        ]);
+        let new = BTreeMap::from([
+            ("Kept".to_string(), fields(&[("heat", "12"), ("color", "FF0000"), ("reagent", "Water")])),
+            ("Fresh".to_string(), fields(&[("heat", "2")])),
+        ]);
+        let changes: Vec<_> = compare_fields(Dataset::Gas, old, new)
+            .into_iter()
+            .map(|difference| (difference.name, difference.field, difference.change, difference.old, difference.new))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("Fresh".to_string(), String::new(), Change::Added, None, Some("heat: 2".to_string())),
+                ("Gone".to_string(), String::new(), Change::Removed, Some("heat: 1".to_string()), None),
+                ("Kept".to_string(), "heat".to_string(), Change::Changed, Some("10".to_string()), Some("12".to_string())),
+                ("Kept".to_string(), "dropped".to_string(), Change::Removed, Some("1".to_string()), None),
+                ("Kept".to_string(), "reagent".to_string(), Change::Added, None, Some("Water".to_string())),
+            ]
+        );
+    }
+}
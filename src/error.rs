@@ -0,0 +1,41 @@
+use std::fmt;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub enum Error {
+    Network { url: String, source: reqwest::Error },
+    Http { url: String, status: reqwest::StatusCode },
+    Io { path: PathBuf, source: std::io::Error },
+    Yaml { file: String, source: serde_yaml::Error },
+    CSharp { file: String, line: Option<usize>, message: String },
+    Git { path: PathBuf, message: String },
+    InvalidSource(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Network { url, source } => write!(f, "Error fetching {}: {}", url, source),
+            Error::Http { url, status } => write!(f, "Error fetching {}: HTTP {}", url, status),
+            Error::Io { path, source } => write!(f, "Error reading {}: {}", path.display(), source),
+            Error::Yaml { file, source } => write!(f, "Error parsing {}: {}", file, source),
+            Error::CSharp { file, line: Some(line), message } => write!(f, "Error parsing {}:{}: {}", file, line, message),
+            Error::CSharp { file, line: None, message } => write!(f, "Error parsing {}: {}", file, message),
+            Error::Git { path, message } => write!(f, "Error reading git history of {}: {}", path.display(), message),
+            Error::InvalidSource(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Network { source, .. } => Some(source),
+            Error::Io { source, .. } => Some(source),
+            Error::Yaml { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use evalexpr::{eval_with_context, ContextWithMutableFunctions, ContextWithMutableVariables, EvalexprError, Function, HashMapContext, Value};
+use regex::Regex;
+use std::collections::HashMap;
+
+const MAX_DEPTH: usize = 64;
+
+pub struct Evaluator<'a> {
+    constants: &'a HashMap<String, String>,
+    user_variables: &'a HashMap<String, String>,
+}
+
+impl<'a> Evaluator<'a> {
+    pub fn new(constants: &'a HashMap<String, String>, user_variables: &'a HashMap<String, String>) -> Self {
+        Self { constants, user_variables }
+    }
+
+    pub fn evaluate_expression(&self, expression: &str, context: &HashMapContext) -> Result<String, String> {
+        self.evaluate_value(expression, context).map(|value| format!("{}", value))
+    }
+
+    fn evaluate_value(&self, expression: &str, context: &HashMapContext) -> Result<Value, String> {
+        eval_with_context(expression, context).map_err(|err| format!("Ошибка при вычислении: {}", err))
+    }
+
+    pub fn create_evaluation_context(&self) -> HashMapContext {
+        let mut context = new_context();
+        self.add_variables_to_context(&mut context, self.constants);
+        self.add_variables_to_context(&mut context, self.user_variables);
+        context
+    }
+
+    fn add_variables_to_context(&self, context: &mut HashMapContext, variables: &HashMap<String, String>) {
+        for (name, value) in variables {
+            let cleaned_value = value.trim_end_matches('f');
+            if let Ok(parsed_value) = value.trim().parse::<i64>() {
+                context.set_value(name.clone(), Value::Int(parsed_value)).unwrap();
+            } else if let Ok(parsed_value) = cleaned_value.parse::<f64>() {
+                context.set_value(name.clone(), Value::Float(parsed_value)).unwrap();
+            } else if let Ok(resolved_value @ (Value::Int(_) | Value::Float(_))) = self.resolve_nested(value, &mut context.clone(), 0) {
+                context.set_value(name.clone(), resolved_value).unwrap();
+            }
+        }
+    }
+
+    pub fn resolve_expression(&self, expression: &str, context: &mut HashMapContext) -> Result<String, String> {
+        self.resolve_nested(expression, context, 0).map(|value| format!("{}", value))
+    }
+
+    pub fn evaluate_constants(&self) -> HashMap<String, Result<String, String>> {
+        let mut context = self.create_evaluation_context();
+        self.constants
+            .keys()
+            .map(|name| (name.clone(), self.resolve_expression(name, &mut context)))
+            .collect()
+    }
+
+    pub fn sweep(&self, expression: &str, variable: &str, values: &[f64]) -> Vec<Result<f64, String>> {
+        let mut constants = self.constants.clone();
+        let mut variables = self.user_variables.clone();
+        values
+            .iter()
+            .map(|value| {
+                // Substituted by name so constants that depend on a swept constant follow it.
+                let target = if self.constants.contains_key(variable) { &mut constants } else { &mut variables };
+                target.insert(variable.to_string(), format!("{:?}", value));
+                let evaluator = Evaluator::new(&constants, &variables);
+                let result = evaluator.resolve_expression(expression, &mut new_context())?;
+                result.parse().map_err(|_| format!("Ошибка при вычислении: {} is not a number", result))
+            })
+            .collect()
+    }
+
+    fn resolve_nested(&self, expression: &str, context: &mut HashMapContext, depth: usize) -> Result<Value, String> {
+        if depth > MAX_DEPTH {
+            return Err(format!("Ошибка при вычислении: circular reference in {}", expression));
+        }
+        let mut expr = expression.to_string();
+        let number_with_f_regex = Regex::new(r"(\d+\.?\d*e[-+]?\d*|\d+\.?\d*)f").unwrap();
+        expr = number_with_f_regex.replace_all(&expr, "$1").to_string();
+
+        let mut unresolved = true;
+        let mut passes = 0;
+        while unresolved {
+            passes += 1;
+            if passes > MAX_DEPTH {
+                return Err(format!("Ошибка при вычислении: circular reference in {}", expression));
+            }
+            unresolved = false;
+            unresolved |= self.replace_variables_in_expression(&mut expr, self.constants, depth)?;
+            unresolved |= self.replace_variables_in_expression(&mut expr, self.user_variables, depth)?;
+        }
+
+        self.evaluate_value(&expr, context)
+    }
+
+    fn replace_variables_in_expression(&self, expr: &mut String, variables: &HashMap<String, String>, depth: usize) -> Result<bool, String> {
+        let mut unresolved = false;
+        for (name, value) in variables {
+            if !expr.contains(name.as_str()) {
+                continue;
+            }
+            let re = Regex::new(&format!(r"\b{}\b", regex::escape(name))).unwrap();
+            // Odd segments are inside string literals and must be left alone.
+            let mut segments: Vec<String> = expr.split('"').map(str::to_string).collect();
+            if segments.iter().step_by(2).any(|segment| re.is_match(segment)) {
+                unresolved = true;
+                let resolved_value = match literal(value) {
+                    Some(literal) => literal,
+                    None => format!("({})", value_literal(&self.resolve_nested(value, &mut new_context(), depth + 1)?)),
+                };
+                for segment in segments.iter_mut().step_by(2) {
+                    *segment = re.replace_all(segment, resolved_value.as_str()).to_string();
+                }
+                *expr = segments.join("\"");
+            }
+        }
+        Ok(unresolved)
+    }
+}
+
+// `int` is what C# integer casts become: it truncates toward zero and yields an integer, which evalexpr's
+// floor and ceil don't.
+fn new_context() -> HashMapContext {
+    let mut context = HashMapContext::new();
+    let int = Function::new(|argument| match argument {
+        Value::Int(int) => Ok(Value::Int(*int)),
+        Value::Float(float) => Ok(Value::Int(float.trunc() as i64)),
+        _ => Err(EvalexprError::expected_number(argument.clone())),
+    });
+    context.set_function("int".to_string(), int).unwrap();
+    context
+}
+
+// Integers stay integers so that C# integer division still truncates; everything else becomes a float literal.
+fn literal(value: &str) -> Option<String> {
+    let value = value.trim();
+    if let Ok(integer) = value.parse::<i64>() {
+        return Some(integer.to_string());
+    }
+    value.trim_end_matches('f').parse::<f64>().ok().map(|float| format!("{:?}", float))
+}
+
+fn value_literal(value: &Value) -> String {
+    match value {
+        Value::Float(float) => format!("{:?}", float),
+        value => format!("{}", value),
+    }
+}
+
+pub fn sweep_values(from: f64, to: f64, steps: usize) -> Vec<f64> {
+    match steps {
+        0 => Vec::new(),
+        1 => vec![from],
+        _ => (0..steps).map(|i| from + (to - from) * i as f64 / (steps - 1) as f64).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(constants: &[(&str, &str)], name: &str) -> Result<String, String> {
+        let constants: HashMap<String, String> = constants.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect();
+        let variables = HashMap::new();
+        Evaluator::new(&constants, &variables).evaluate_constants().remove(name).unwrap()
+    }
+
+    #[test]
+    fn integer_constants_keep_integer_division() {
+        assert_eq!(evaluate(&[("Whole", "5"), ("Half", "(Whole / 2)")], "Half"), Ok("2".to_string()));
+    }
+
+    #[test]
+    fn float_constants_keep_float_division() {
+        assert_eq!(evaluate(&[("Whole", "5.0"), ("Half", "(Whole / 2)")], "Half"), Ok("2.5".to_string()));
+        assert_eq!(evaluate(&[("Whole", "5f"), ("Half", "(Whole / 2)")], "Half"), Ok("2.5".to_string()));
+    }
+
+    #[test]
+    fn nested_float_results_stay_floats() {
+        let constants = [("Two", "(4.0 / 2.0)"), ("Five", "5"), ("Ratio", "(Five / Two)")];
+        assert_eq!(evaluate(&constants, "Ratio"), Ok("2.5".to_string()));
+    }
+
+    #[test]
+    fn integer_casts_truncate_toward_zero() {
+        assert_eq!(evaluate(&[("Negative", "int(-1.5)")], "Negative"), Ok("-1".to_string()));
+        assert_eq!(evaluate(&[("Positive", "int(2.7)")], "Positive"), Ok("2".to_string()));
+        let constants = [("Rate", "-3.5"), ("Steps", "int(Rate)"), ("Half", "(Steps / 2)")];
+        assert_eq!(evaluate(&constants, "Half"), Ok("-1".to_string()));
+    }
+}
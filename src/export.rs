@@ -0,0 +1,320 @@
+use crate::reaction::Requirement;
+use crate::{Constant, Gas, Reaction};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Csv,
+    Json,
+    Markdown,
+    MediaWiki,
+}
+
+impl Format {
+    pub const ALL: [Format; 4] = [Format::Csv, Format::Json, Format::Markdown, Format::MediaWiki];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "csv" => Some(Format::Csv),
+            "json" => Some(Format::Json),
+            "markdown" | "md" => Some(Format::Markdown),
+            "mediawiki" | "wiki" => Some(Format::MediaWiki),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Csv => "csv",
+            Format::Json => "json",
+            Format::Markdown => "md",
+            Format::MediaWiki => "wiki",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cell {
+    Empty,
+    Text(String),
+    Number(f64),
+    Bool(bool),
+    List(Vec<String>),
+}
+
+impl Cell {
+    fn optional<T: Into<f64>>(value: Option<T>) -> Self {
+        value.map_or(Cell::Empty, |value| Cell::Number(value.into()))
+    }
+
+    fn to_json(&self) -> Value {
+        match self {
+            Cell::Empty => Value::Null,
+            Cell::Text(text) => Value::String(text.clone()),
+            Cell::Number(number) if number.fract() == 0.0 && number.abs() < 9e15 => Value::from(*number as i64),
+            Cell::Number(number) => serde_json::Number::from_f64(*number).map_or(Value::Null, Value::Number),
+            Cell::Bool(value) => Value::Bool(*value),
+            Cell::List(items) => Value::Array(items.iter().cloned().map(Value::String).collect()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Table {
+    pub title: &'static str,
+    pub columns: Vec<(&'static str, &'static str)>,
+    pub rows: Vec<Vec<Cell>>,
+}
+
+impl Table {
+    pub fn constants<'a>(constants: impl IntoIterator<Item = &'a Constant>, evaluated: &HashMap<String, Result<String, String>>) -> Self {
+        let rows = constants
+            .into_iter()
+            .map(|constant| {
+                let (value, error) = match evaluated.get(&constant.name) {
+                    Some(Ok(value)) => (value.parse().map_or_else(|_| Cell::Text(value.clone()), Cell::Number), Cell::Empty),
+                    Some(Err(err)) => (Cell::Empty, Cell::Text(err.clone())),
+                    None => (Cell::Empty, Cell::Empty),
+                };
+                vec![
+                    Cell::Text(constant.name.clone()),
+                    Cell::Text(constant.class.clone()),
+                    Cell::Text(constant.ty.clone()),
+                    Cell::Bool(constant.readonly),
+                    Cell::Text(constant.initializer.clone()),
+                    value,
+                    error,
+                    Cell::List(constant.references.clone()),
+                    constant.description.clone().map_or(Cell::Empty, Cell::Text),
+                    Cell::Number(constant.line as f64),
+                ]
+            })
+            .collect();
+        Self {
+            title: "Atmos constants",
+            columns: vec![
+                ("name", "Constant Name"),
+                ("class", "Class"),
+                ("type", "Type"),
+                ("readonly", "Readonly"),
+                ("value", "Value"),
+                ("evaluated", "Evaluated"),
+                ("error", "Error"),
+                ("depends_on", "Depends On"),
+                ("description", "Description"),
+                ("line", "Line"),
+            ],
+            rows,
+        }
+    }
+
+    pub fn gases(gases: &[Gas]) -> Self {
+        let rows = gases
+            .iter()
+            .map(|gas| {
+                vec![
+                    gas.id.clone().map_or(Cell::Empty, Cell::Text),
+                    Cell::Text(gas.name.clone()),
+                    Cell::optional(gas.specific_heat),
+                    Cell::optional(gas.heat_capacity_ratio),
+                    Cell::optional(gas.molar_mass),
+                    gas.gas_overlay_sprite.clone().map_or(Cell::Empty, Cell::Text),
+                    gas.gas_overlay_state.clone().map_or(Cell::Empty, Cell::Text),
+                    Cell::optional(gas.gas_moles_visible),
+                    gas.color.clone().map_or(Cell::Empty, Cell::Text),
+                    gas.reagent.clone().map_or(Cell::Empty, Cell::Text),
+                    Cell::optional(gas.price_per_mole),
+                    Cell::List(gas.other_fields().into_iter().map(|(key, value)| format!("{}: {}", key, value)).collect()),
+                ]
+            })
+            .collect();
+        Self {
+            title: "Gases",
+            columns: vec![
+                ("id", "ID"),
+                ("name", "Gas Name"),
+                ("specific_heat", "Specific Heat"),
+                ("heat_capacity_ratio", "Heat Capacity Ratio"),
+                ("molar_mass", "Molar Mass"),
+                ("gas_overlay_sprite", "Overlay Sprite"),
+                ("gas_overlay_state", "Overlay State"),
+                ("gas_moles_visible", "Moles Visible"),
+                ("color", "Color"),
+                ("reagent", "Reagent"),
+                ("price_per_mole", "Price Per Mole"),
+                ("other", "Other"),
+            ],
+            rows,
+        }
+    }
+
+    pub fn reactions(reactions: &[Reaction], gases: &[String]) -> Self {
+        let rows = reactions
+            .iter()
+            .map(|reaction| {
+                vec![
+                    Cell::Text(reaction.id.clone()),
+                    Cell::optional(reaction.priority),
+                    Cell::optional(reaction.minimum_temperature),
+                    Cell::optional(reaction.maximum_temperature),
+                    Cell::List(reaction.requirements(gases).iter().map(Requirement::to_string).collect()),
+                    Cell::List(reaction.effects.iter().map(|effect| effect.describe(gases)).collect()),
+                ]
+            })
+            .collect();
+        Self {
+            title: "Gas reactions",
+            columns: vec![
+                ("id", "Reaction ID"),
+                ("priority", "Priority"),
+                ("minimum_temperature", "Minimum Temperature"),
+                ("maximum_temperature", "Maximum Temperature"),
+                ("minimum_requirements", "Minimum Requirements"),
+                ("effects", "Effects"),
+            ],
+            rows,
+        }
+    }
+
+    pub fn headers(&self) -> Vec<&'static str> {
+        self.columns.iter().map(|(_, title)| *title).collect()
+    }
+
+    pub fn text_rows(&self) -> Vec<Vec<String>> {
+        self.rows.iter().map(|row| row.iter().map(Cell::to_string).collect()).collect()
+    }
+
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Csv => self.csv(),
+            Format::Json => self.json(),
+            Format::Markdown => self.markdown(),
+            Format::MediaWiki => self.mediawiki(),
+        }
+    }
+
+    fn csv(&self) -> String {
+        let line = |cells: Vec<String>| format!("{}\r\n", cells.iter().map(|cell| csv_escape(cell)).collect::<Vec<_>>().join(","));
+        let mut text = line(self.headers().into_iter().map(str::to_string).collect());
+        for row in self.text_rows() {
+            text.push_str(&line(row));
+        }
+        text
+    }
+
+    fn json(&self) -> String {
+        let records: Vec<Value> = self
+            .rows
+            .iter()
+            .map(|row| Value::Object(self.columns.iter().zip(row).map(|((key, _), cell)| (key.to_string(), cell.to_json())).collect::<Map<_, _>>()))
+            .collect();
+        serde_json::to_string_pretty(&records).unwrap_or_default() + "\n"
+    }
+
+    fn markdown(&self) -> String {
+        let escape = |cell: &str| cell.replace('|', "\\|").replace('\n', "<br>");
+        let mut text = format!("| {} |\n", self.headers().join(" | "));
+        text.push_str(&format!("|{}\n", "---|".repeat(self.columns.len())));
+        for row in self.text_rows() {
+            text.push_str(&format!("| {} |\n", row.iter().map(|cell| escape(cell)).collect::<Vec<_>>().join(" | ")));
+        }
+        text
+    }
+
+    fn mediawiki(&self) -> String {
+        let escape = |cell: &str| cell.replace('|', "&#124;").replace('\n', "<br>");
+        let mut text = format!("{{| class=\"wikitable sortable\"\n|+ {}\n! {}\n", self.title, self.headers().join(" !! "));
+        for row in self.text_rows() {
+            text.push_str(&format!("|-\n| {}\n", row.iter().map(|cell| escape(cell)).collect::<Vec<_>>().join(" || ")));
+        }
+        text.push_str("|}\n");
+        text
+    }
+}
+
+// Quotes a CSV cell when it holds a separator, quote or line break, doubling any quotes inside.
+pub fn csv_escape(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Format::Csv => "CSV",
+            Format::Json => "JSON",
+            Format::Markdown => "Markdown",
+            Format::MediaWiki => "MediaWiki",
+        })
+    }
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cell::Empty => Ok(()),
+            Cell::Text(text) => f.write_str(text),
+            Cell::Number(number) => write!(f, "{}", number),
+            Cell::Bool(value) => write!(f, "{}", value),
+            Cell::List(items) => f.write_str(&items.join(", ")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> Table {
+        Table {
+            title: "Gases",
+            columns: vec![("name", "Name"), ("note", "Note"), ("amount", "Amount"), ("tags", "Tags")],
+            rows: vec![
+                vec![Cell::Text("a|b".to_string()), Cell::Text("say \"hi\", twice".to_string()), Cell::Number(1.5), Cell::List(vec!["x".to_string(), "y".to_string()])],
+                vec![Cell::Text("line\nbreak".to_string()), Cell::Empty, Cell::Number(2.0), Cell::Bool(true)],
+            ],
+        }
+    }
+
+    #[test]
+    fn renders_csv_with_quoted_cells() {
+        assert_eq!(
+            table().render(Format::Csv),
+            "Name,Note,Amount,Tags\r\na|b,\"say \"\"hi\"\", twice\",1.5,\"x, y\"\r\n\"line\nbreak\",,2,true\r\n"
+        );
+    }
+
+    #[test]
+    fn renders_json_records_keyed_by_column() {
+        let records: Value = serde_json::from_str(&table().render(Format::Json)).unwrap();
+        assert_eq!(
+            records,
+            serde_json::json!([
+                { "name": "a|b", "note": "say \"hi\", twice", "amount": 1.5, "tags": ["x", "y"] },
+                { "name": "line\nbreak", "note": null, "amount": 2, "tags": true },
+            ])
+        );
+    }
+
+    #[test]
+    fn renders_markdown_with_escaped_pipes() {
+        assert_eq!(
+            table().render(Format::Markdown),
+            "| Name | Note | Amount | Tags |\n|---|---|---|---|\n| a\\|b | say \"hi\", twice | 1.5 | x, y |\n| line<br>break |  | 2 | true |\n"
+        );
+    }
+
+    #[test]
+    fn renders_mediawiki_with_escaped_pipes() {
+        assert_eq!(
+            table().render(Format::MediaWiki),
+            "{| class=\"wikitable sortable\"\n|+ Gases\n! Name !! Note !! Amount !! Tags\n|-\n| a&#124;b || say \"hi\", twice || 1.5 || x, y\n|-\n| line<br>break ||  || 2 || true\n|}\n"
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use crate::constants::{parse_constants, parse_enum, Constant};
+use crate::error::{Error, Result};
+use crate::gas::{parse_gases, Gas};
+use crate::reaction::{parse_reactions, Reaction};
+use crate::source::{DataSource, Origin, GASES_FILE, REACTIONS_FILE, TEG_PATH};
+use crate::teg::{parse_generator, Generator};
+
+pub fn fetch_and_parse_constants(source: &DataSource) -> Result<(Vec<Constant>, Origin)> {
+    let (file, origin) = source.constants_text()?;
+    let constants = parse_constants(&file.text).map_err(|err| Error::CSharp {
+        file: file.name.clone(),
+        line: Some(err.line),
+        message: err.message,
+    })?;
+    if constants.is_empty() {
+        return Err(Error::CSharp {
+            file: file.name,
+            line: None,
+            message: "no constant declarations found".to_string(),
+        });
+    }
+    Ok((constants, origin))
+}
+
+pub fn fetch_and_parse_gases(source: &DataSource) -> Result<(Vec<Gas>, Origin)> {
+    let (files, origin) = source.prototype_texts(GASES_FILE)?;
+    let mut gases = Vec::new();
+    for file in files {
+        gases.extend(parse_gases(&file.text).map_err(|source| Error::Yaml { file: file.name, source })?);
+    }
+    Ok((gases, origin))
+}
+
+pub fn fetch_and_parse_reactions(source: &DataSource) -> Result<(Vec<Reaction>, Origin)> {
+    let (files, origin) = source.prototype_texts(REACTIONS_FILE)?;
+    let mut reactions = Vec::new();
+    for file in files {
+        reactions.extend(parse_reactions(&file.text).map_err(|source| Error::Yaml { file: file.name, source })?);
+    }
+    Ok((reactions, origin))
+}
+
+pub fn fetch_gas_names(source: &DataSource) -> Result<Vec<String>> {
+    let (file, _) = source.constants_text()?;
+    let names = parse_enum(&file.text, "Gas").map_err(|err| Error::CSharp {
+        file: file.name,
+        line: Some(err.line),
+        message: err.message,
+    })?;
+    if !names.is_empty() {
+        return Ok(names);
+    }
+    let (mut gases, _) = fetch_and_parse_gases(source)?;
+    gases.sort_by_key(|gas| gas.id.as_deref().and_then(|id| id.parse::<i64>().ok()).unwrap_or(i64::MAX));
+    Ok(gases.into_iter().map(|gas| gas.name).collect())
+}
+
+// None when the source has no TEG prototype, e.g. a fork that moved or removed it.
+pub fn fetch_generator(source: &DataSource) -> Result<(Option<Generator>, Origin)> {
+    let (file, origin) = match source.file_text(TEG_PATH) {
+        Ok(loaded) => loaded,
+        Err(Error::Http { status, .. }) if status == reqwest::StatusCode::NOT_FOUND => return Ok((None, Origin::new(source.clone()))),
+        Err(Error::Io { source: err, .. }) if err.kind() == std::io::ErrorKind::NotFound => return Ok((None, Origin::new(source.clone()))),
+        Err(err) => return Err(err),
+    };
+    let generator = parse_generator(&file.text).map_err(|source| Error::Yaml { file: file.name, source })?;
+    Ok((generator, origin))
+}
@@ -0,0 +1,156 @@
+use serde::de::{DeserializeSeed, IgnoredAny, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+use serde_yaml::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Gas {
+    #[serde(default, deserialize_with = "scalar")]
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(rename = "specificHeat")]
+    pub specific_heat: Option<f64>,
+    #[serde(rename = "heatCapacityRatio")]
+    pub heat_capacity_ratio: Option<f64>,
+    #[serde(rename = "molarMass")]
+    pub molar_mass: Option<f64>,
+    #[serde(rename = "gasOverlaySprite")]
+    pub gas_overlay_sprite: Option<String>,
+    #[serde(rename = "gasOverlayState")]
+    pub gas_overlay_state: Option<String>,
+    #[serde(rename = "gasMolesVisible")]
+    pub gas_moles_visible: Option<f64>,
+    #[serde(default, deserialize_with = "scalar")]
+    pub color: Option<String>,
+    pub reagent: Option<String>,
+    #[serde(rename = "pricePerMole")]
+    pub price_per_mole: Option<f64>,
+    #[serde(flatten)]
+    pub other: BTreeMap<String, Value>,
+}
+
+impl Gas {
+    pub fn fields(&self) -> Vec<(&'static str, Option<String>)> {
+        let number = |value: Option<f64>| value.map(|value| value.to_string());
+        vec![
+            ("id", self.id.clone()),
+            ("specificHeat", number(self.specific_heat)),
+            ("heatCapacityRatio", number(self.heat_capacity_ratio)),
+            ("molarMass", number(self.molar_mass)),
+            ("gasOverlaySprite", self.gas_overlay_sprite.clone()),
+            ("gasOverlayState", self.gas_overlay_state.clone()),
+            ("gasMolesVisible", number(self.gas_moles_visible)),
+            ("color", self.color.clone()),
+            ("reagent", self.reagent.clone()),
+            ("pricePerMole", number(self.price_per_mole)),
+        ]
+    }
+
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        let name = self.name.to_lowercase();
+        name == query
+            || name.strip_prefix("gases-") == Some(query.as_str())
+            || self.reagent.as_ref().is_some_and(|reagent| reagent.to_lowercase() == query)
+            || self.id.as_deref() == Some(query.as_str())
+    }
+
+    pub fn other_fields(&self) -> Vec<(String, String)> {
+        self.other
+            .iter()
+            .map(|(key, value)| match value {
+                Value::String(text) => (key.clone(), text.clone()),
+                _ => (key.clone(), serde_json::to_string(value).unwrap_or_default()),
+            })
+            .collect()
+    }
+}
+
+// Plain scalars are read as their source text, so a hex colour such as 2887E8 isn't turned into a number.
+fn scalar<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    Option::<String>::deserialize(deserializer)
+}
+
+pub fn parse_gases(text: &str) -> Result<Vec<Gas>, serde_yaml::Error> {
+    let docs: Vec<Value> = serde_yaml::from_str(text)?;
+    let wanted: Vec<bool> = docs
+        .iter()
+        .map(|doc| doc.get("type").and_then(Value::as_str).is_none_or(|kind| kind == "gas"))
+        .collect();
+    if !wanted.contains(&true) {
+        return Ok(Vec::new());
+    }
+    // Value has already turned scalars like 2887E8 into numbers, so the gases are read again from the text.
+    let mut gases = GasDocuments(wanted).deserialize(serde_yaml::Deserializer::from_str(text))?;
+    for gas in &mut gases {
+        gas.other.remove("type");
+    }
+    Ok(gases)
+}
+
+// Deserializes the documents flagged as gases and skips the rest, which may use tags a Gas can't hold.
+struct GasDocuments(Vec<bool>);
+
+impl<'de> DeserializeSeed<'de> for GasDocuments {
+    type Value = Vec<Gas>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for GasDocuments {
+    type Value = Vec<Gas>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list of prototypes")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut gases = Vec::new();
+        for wanted in self.0 {
+            if wanted {
+                match seq.next_element::<Gas>()? {
+                    Some(gas) => gases.push(gas),
+                    None => break,
+                }
+            } else if seq.next_element::<IgnoredAny>()?.is_none() {
+                break;
+            }
+        }
+        Ok(gases)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_scalar_text_of_id_and_color() {
+        let text = "
+- type: gas
+  id: 0
+  name: gases-oxygen
+  specificHeat: 20
+  color: 2887E8
+- type: gas
+  id: 1
+  name: gases-nitrogen
+  color: DA1010
+  flags: [a, b]
+- type: gasReaction
+  id: PlasmaFire
+  effects:
+  - !type:PlasmaFireReaction {}
+";
+        let gases = parse_gases(text).unwrap();
+        assert_eq!(gases.len(), 2);
+        assert_eq!(gases[0].id.as_deref(), Some("0"));
+        assert_eq!(gases[0].color.as_deref(), Some("2887E8"));
+        assert_eq!(gases[0].specific_heat, Some(20.0));
+        assert_eq!(gases[1].color.as_deref(), Some("DA1010"));
+        assert_eq!(gases[1].other_fields(), [("flags".to_string(), "[\"a\",\"b\"]".to_string())]);
+    }
+}
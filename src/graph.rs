@@ -0,0 +1,131 @@
+use crate::Constant;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    dependencies: BTreeMap<String, Vec<String>>,
+    dependents: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dangling {
+    pub constant: String,
+    pub reference: String,
+}
+
+impl DependencyGraph {
+    pub fn new(constants: &[Constant]) -> Self {
+        let mut graph = Self::default();
+        for constant in constants {
+            for reference in &constant.references {
+                graph.dependents.entry(reference.clone()).or_default().push(constant.name.clone());
+            }
+            graph.dependencies.insert(constant.name.clone(), constant.references.clone());
+        }
+        graph
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.dependencies.contains_key(name)
+    }
+
+    pub fn dependencies(&self, name: &str) -> &[String] {
+        self.dependencies.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn dependents(&self, name: &str) -> &[String] {
+        self.dependents.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn all_dependencies(&self, name: &str) -> BTreeSet<String> {
+        self.reachable(name, |name| self.dependencies(name))
+    }
+
+    pub fn all_dependents(&self, name: &str) -> BTreeSet<String> {
+        self.reachable(name, |name| self.dependents(name))
+    }
+
+    pub fn dangling(&self) -> Vec<Dangling> {
+        self.dependencies
+            .iter()
+            .flat_map(|(constant, references)| {
+                references.iter().filter(|reference| !self.contains(reference)).map(|reference| Dangling {
+                    constant: constant.clone(),
+                    reference: reference.clone(),
+                })
+            })
+            .collect()
+    }
+
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        let mut tarjan = Tarjan::default();
+        for name in self.dependencies.keys() {
+            if !tarjan.index.contains_key(name.as_str()) {
+                tarjan.visit(self, name);
+            }
+        }
+        tarjan
+            .components
+            .into_iter()
+            .filter(|component| component.len() > 1 || self.dependencies(&component[0]).contains(&component[0]))
+            .collect()
+    }
+
+    pub fn in_cycle(&self, name: &str) -> bool {
+        self.all_dependencies(name).contains(name)
+    }
+
+    fn reachable<'a>(&'a self, name: &str, edges: impl Fn(&str) -> &'a [String]) -> BTreeSet<String> {
+        let mut seen = BTreeSet::new();
+        let mut stack: Vec<&str> = edges(name).iter().map(String::as_str).collect();
+        while let Some(next) = stack.pop() {
+            if seen.insert(next.to_string()) {
+                stack.extend(edges(next).iter().map(String::as_str));
+            }
+        }
+        seen
+    }
+}
+
+#[derive(Default)]
+struct Tarjan<'a> {
+    index: BTreeMap<&'a str, usize>,
+    lowlink: BTreeMap<&'a str, usize>,
+    stack: Vec<&'a str>,
+    on_stack: BTreeSet<&'a str>,
+    components: Vec<Vec<String>>,
+}
+
+impl<'a> Tarjan<'a> {
+    fn visit(&mut self, graph: &'a DependencyGraph, name: &'a str) {
+        let index = self.index.len();
+        self.index.insert(name, index);
+        self.lowlink.insert(name, index);
+        self.stack.push(name);
+        self.on_stack.insert(name);
+
+        for next in graph.dependencies(name).iter().filter(|next| graph.contains(next)) {
+            if !self.index.contains_key(next.as_str()) {
+                self.visit(graph, next);
+                let low = self.lowlink[name].min(self.lowlink[next.as_str()]);
+                self.lowlink.insert(name, low);
+            } else if self.on_stack.contains(next.as_str()) {
+                let low = self.lowlink[name].min(self.index[next.as_str()]);
+                self.lowlink.insert(name, low);
+            }
+        }
+
+        if self.lowlink[name] == index {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack.remove(member);
+                component.push(member.to_string());
+                if member == name {
+                    break;
+                }
+            }
+            component.sort();
+            self.components.push(component);
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use crate::constants::{expressions, parse_constants};
+use crate::diff::{gas_fields, reaction_fields, Dataset};
+use crate::error::{Error, Result};
+use crate::gas::parse_gases;
+use crate::reaction::parse_reactions;
+use crate::source::{CONSTANTS_PATH, GASES_FILE, PROTOTYPES_DIR, REACTIONS_FILE};
+use crate::Evaluator;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::process::Command;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Revision {
+    pub commit: String,
+    pub date: String,
+    pub summary: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Point {
+    pub revision: usize,
+    pub raw: Option<String>,
+    pub value: Option<String>,
+}
+
+impl Point {
+    pub fn number(&self) -> Option<f64> {
+        self.value.as_deref().or(self.raw.as_deref())?.trim_end_matches('f').parse().ok()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Timeline {
+    pub dataset: Dataset,
+    pub name: String,
+    pub field: String,
+    pub points: Vec<Point>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    pub revisions: Vec<Revision>,
+    pub timelines: Vec<Timeline>,
+}
+
+impl History {
+    pub fn find<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Timeline> {
+        self.timelines.iter().filter(move |timeline| timeline.name == name)
+    }
+}
+
+type Key = (Dataset, String, String);
+type Entries = BTreeMap<Key, (Option<String>, Option<String>)>;
+
+pub fn history(root: &Path, limit: Option<usize>) -> Result<History> {
+    let gases_path = format!("{}/{}", PROTOTYPES_DIR, GASES_FILE);
+    let reactions_path = format!("{}/{}", PROTOTYPES_DIR, REACTIONS_FILE);
+    let mut args = vec!["log".to_string(), "--reverse".to_string(), "--format=%H%x09%cs%x09%s".to_string()];
+    if let Some(limit) = limit {
+        args.push(format!("--max-count={}", limit));
+    }
+    args.extend(["--".to_string(), CONSTANTS_PATH.to_string(), gases_path.clone(), reactions_path.clone()]);
+    let log = git(root, &args)?;
+
+    let mut history = History::default();
+    let mut timelines: BTreeMap<Key, Vec<Point>> = BTreeMap::new();
+    let mut files: [(String, Entries); 3] = Default::default();
+    let mut previous = Entries::new();
+
+    for line in log.lines() {
+        let mut parts = line.splitn(3, '\t');
+        let (Some(commit), Some(date), summary) = (parts.next(), parts.next(), parts.next()) else {
+            continue;
+        };
+        let revision = history.revisions.len();
+        history.revisions.push(Revision {
+            commit: commit.to_string(),
+            date: date.to_string(),
+            summary: summary.unwrap_or("").to_string(),
+        });
+
+        for (i, path) in [CONSTANTS_PATH, gases_path.as_str(), reactions_path.as_str()].into_iter().enumerate() {
+            // Files that fail to load or parse at a revision keep their previous values.
+            let text = git(root, &["show".to_string(), format!("{}:./{}", commit, path)]).unwrap_or_default();
+            if text == files[i].0 {
+                continue;
+            }
+            let entries = match i {
+                0 => constant_entries(&text),
+                1 => prototype_entries(&text, Dataset::Gas),
+                _ => prototype_entries(&text, Dataset::Reaction),
+            };
+            if let Some(entries) = entries.or_else(|| text.is_empty().then(Entries::new)) {
+                files[i] = (text, entries);
+            }
+        }
+
+        let current: Entries = files.iter().flat_map(|(_, entries)| entries.clone()).collect();
+        for key in previous.keys().chain(current.keys()) {
+            let (old, new) = (previous.get(key), current.get(key));
+            if old == new {
+                continue;
+            }
+            let points = timelines.entry(key.clone()).or_default();
+            if points.last().is_some_and(|point| point.revision == revision) {
+                continue;
+            }
+            let (raw, value) = new.cloned().unwrap_or((None, None));
+            points.push(Point { revision, raw, value });
+        }
+        previous = current;
+    }
+
+    history.timelines = timelines
+        .into_iter()
+        .map(|((dataset, name, field), points)| Timeline { dataset, name, field, points })
+        .collect();
+    Ok(history)
+}
+
+fn constant_entries(text: &str) -> Option<Entries> {
+    let constants = parse_constants(text).ok()?;
+    let expressions = expressions(&constants);
+    let variables = HashMap::new();
+    let mut values = Evaluator::new(&expressions, &variables).evaluate_constants();
+    Some(
+        constants
+            .into_iter()
+            .map(|constant| {
+                let value = values.remove(&constant.name).map(|value| value.unwrap_or_else(|err| err));
+                ((Dataset::Constant, constant.name, "value".to_string()), (Some(constant.initializer), value))
+            })
+            .collect(),
+    )
+}
+
+fn prototype_entries(text: &str, dataset: Dataset) -> Option<Entries> {
+    let prototypes: Vec<(String, Vec<(&str, String)>)> = match dataset {
+        Dataset::Gas => parse_gases(text).ok()?.iter().map(|gas| (gas.name.clone(), gas_fields(gas))).collect(),
+        _ => parse_reactions(text).ok()?.iter().map(|reaction| (reaction.id.clone(), reaction_fields(reaction))).collect(),
+    };
+    Some(
+        prototypes
+            .into_iter()
+            .flat_map(|(name, fields)| fields.into_iter().map(move |(field, value)| ((dataset, name.clone(), field.to_string()), (Some(value), None))))
+            .collect(),
+    )
+}
+
+fn git(root: &Path, args: &[String]) -> Result<String> {
+    let output = Command::new("git").arg("-C").arg(root).args(args).output().map_err(|err| Error::Git {
+        path: root.to_path_buf(),
+        message: err.to_string(),
+    })?;
+    if !output.status.success() {
+        return Err(Error::Git {
+            path: root.to_path_buf(),
+            message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -0,0 +1,28 @@
+pub mod cache;
+pub mod chamber;
+pub mod constants;
+pub mod csharp;
+pub mod diff;
+pub mod error;
+pub mod eval;
+pub mod export;
+pub mod fetch;
+pub mod gas;
+pub mod graph;
+pub mod history;
+pub mod mixture;
+pub mod phase;
+pub mod reaction;
+pub mod simulation;
+pub mod source;
+pub mod teg;
+pub mod variables;
+
+pub use constants::{parse_constants, Constant};
+pub use error::{Error, Result};
+pub use eval::Evaluator;
+pub use fetch::{fetch_and_parse_constants, fetch_and_parse_gases, fetch_and_parse_reactions, fetch_gas_names, fetch_generator};
+pub use gas::{parse_gases, Gas};
+pub use graph::DependencyGraph;
+pub use reaction::{parse_reactions, Reaction};
+pub use source::{DataSource, Origin};
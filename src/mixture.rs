@@ -0,0 +1,260 @@
+use crate::Gas;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Physics {
+    pub r: f64,
+    pub heat_scale: f64,
+    pub minimum_heat_capacity: f64,
+    pub minimum_temperature_delta: f64,
+    pub room_temperature: f64,
+    pub cell_volume: f64,
+}
+
+impl Physics {
+    pub fn from_constants(values: &HashMap<String, Result<String, String>>) -> Result<Self, String> {
+        let value = |name: &str| -> Result<Option<f64>, String> {
+            match values.get(name) {
+                Some(Ok(value)) => value.parse().map(Some).map_err(|_| format!("constant {} is not a number: {}", name, value)),
+                Some(Err(err)) => Err(format!("constant {} could not be evaluated: {}", name, err)),
+                None => Ok(None),
+            }
+        };
+        Ok(Self {
+            r: value("R")?.ok_or("constant R is missing")?,
+            heat_scale: value("HeatScale")?.unwrap_or(1.0),
+            minimum_heat_capacity: value("MinimumHeatCapacity")?.unwrap_or(0.0),
+            minimum_temperature_delta: value("MinimumTemperatureDeltaToConsider")?.unwrap_or(0.01),
+            room_temperature: value("T20C")?.unwrap_or(293.15),
+            cell_volume: value("CellVolume")?.unwrap_or(2500.0),
+        })
+    }
+
+    pub fn specific_heat(&self, gas: &Gas) -> f64 {
+        gas.specific_heat.unwrap_or(0.0) / self.heat_scale
+    }
+
+    pub fn moles_for_pressure(&self, pressure: f64, temperature: f64, volume: f64) -> f64 {
+        if temperature <= 0.0 {
+            return 0.0;
+        }
+        pressure * volume / (self.r * temperature)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mixture {
+    pub moles: Vec<f64>,
+    pub temperature: f64,
+    pub volume: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Component {
+    pub gas: String,
+    pub moles: f64,
+    pub fraction: f64,
+    pub partial_pressure: f64,
+    pub heat_capacity: f64,
+    pub mass: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Properties {
+    pub temperature: f64,
+    pub volume: f64,
+    pub total_moles: f64,
+    pub pressure: f64,
+    pub heat_capacity: f64,
+    pub thermal_energy: f64,
+    pub mass: f64,
+    pub molar_mass: Option<f64>,
+    pub heat_capacity_ratio: Option<f64>,
+    pub components: Vec<Component>,
+}
+
+impl Mixture {
+    pub fn new(gases: usize, temperature: f64, volume: f64) -> Self {
+        Self {
+            moles: vec![0.0; gases],
+            temperature,
+            volume,
+        }
+    }
+
+    pub fn total_moles(&self) -> f64 {
+        self.moles.iter().sum()
+    }
+
+    pub fn heat_capacity(&self, gases: &[Gas], physics: &Physics) -> f64 {
+        let capacity: f64 = self.moles.iter().zip(gases).map(|(moles, gas)| moles * physics.specific_heat(gas)).sum();
+        capacity.max(physics.minimum_heat_capacity)
+    }
+
+    // GetThermalEnergy in the game multiplies by the heat capacity before HeatScale is applied.
+    pub fn unscaled_thermal_energy(&self, gases: &[Gas], physics: &Physics) -> f64 {
+        let capacity: f64 = self.moles.iter().zip(gases).map(|(moles, gas)| moles * gas.specific_heat.unwrap_or(0.0)).sum();
+        capacity.max(physics.minimum_heat_capacity) * self.temperature
+    }
+
+    pub fn pressure(&self, physics: &Physics) -> f64 {
+        if self.volume <= 0.0 {
+            return 0.0;
+        }
+        self.total_moles() * physics.r * self.temperature / self.volume
+    }
+
+    pub fn thermal_energy(&self, gases: &[Gas], physics: &Physics) -> f64 {
+        self.heat_capacity(gases, physics) * self.temperature
+    }
+
+    // Same as AtmosphereSystem.Merge: the receiver keeps its volume and takes the heat-capacity-weighted temperature.
+    pub fn merge(&mut self, giver: &Mixture, gases: &[Gas], physics: &Physics) {
+        if (self.temperature - giver.temperature).abs() > physics.minimum_temperature_delta {
+            let receiver_capacity = self.heat_capacity(gases, physics);
+            let giver_capacity = giver.heat_capacity(gases, physics);
+            let combined_capacity = receiver_capacity + giver_capacity;
+            if combined_capacity > physics.minimum_heat_capacity {
+                self.temperature = (giver.temperature * giver_capacity + self.temperature * receiver_capacity) / combined_capacity;
+            }
+        }
+        if self.moles.len() < giver.moles.len() {
+            self.moles.resize(giver.moles.len(), 0.0);
+        }
+        for (moles, added) in self.moles.iter_mut().zip(&giver.moles) {
+            *moles += added;
+        }
+    }
+
+    pub fn properties(&self, gases: &[Gas], physics: &Physics) -> Properties {
+        let total_moles = self.total_moles();
+        let pressure = self.pressure(physics);
+        let heat_capacity = self.heat_capacity(gases, physics);
+        let components: Vec<Component> = self
+            .moles
+            .iter()
+            .zip(gases)
+            .filter(|(moles, _)| **moles > 0.0)
+            .map(|(moles, gas)| {
+                let fraction = moles / total_moles;
+                Component {
+                    gas: gas.name.clone(),
+                    moles: *moles,
+                    fraction,
+                    partial_pressure: pressure * fraction,
+                    heat_capacity: moles * physics.specific_heat(gas),
+                    mass: moles * gas.molar_mass.unwrap_or(0.0),
+                }
+            })
+            .collect();
+        let mass = components.iter().map(|component| component.mass).sum();
+        let weighted = |value: fn(&Gas) -> Option<f64>| -> Option<f64> {
+            let mut sum = 0.0;
+            for (moles, gas) in self.moles.iter().zip(gases).filter(|(moles, _)| **moles > 0.0) {
+                sum += moles * value(gas)?;
+            }
+            (total_moles > 0.0).then(|| sum / total_moles)
+        };
+        Properties {
+            temperature: self.temperature,
+            volume: self.volume,
+            total_moles,
+            pressure,
+            heat_capacity,
+            thermal_energy: heat_capacity * self.temperature,
+            mass,
+            molar_mass: weighted(|gas| gas.molar_mass),
+            heat_capacity_ratio: weighted(|gas| gas.heat_capacity_ratio),
+            components,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gas::parse_gases;
+
+    fn gases() -> Vec<Gas> {
+        parse_gases(
+            "- type: gas\n  id: 0\n  name: Oxygen\n  specificHeat: 20\n  heatCapacityRatio: 1.4\n  molarMass: 32\n\
+             - type: gas\n  id: 1\n  name: Plasma\n  specificHeat: 200\n  heatCapacityRatio: 1.7\n  molarMass: 120\n",
+        )
+        .unwrap()
+    }
+
+    fn physics() -> Physics {
+        Physics {
+            r: 8.314462618,
+            heat_scale: 8.0,
+            minimum_heat_capacity: 0.0003,
+            minimum_temperature_delta: 0.01,
+            room_temperature: 293.15,
+            cell_volume: 2500.0,
+        }
+    }
+
+    #[test]
+    fn heat_capacity_is_scaled_and_clamped() {
+        let (gases, physics) = (gases(), physics());
+        let mixture = Mixture { moles: vec![4.0, 1.0], temperature: 300.0, volume: 2500.0 };
+        assert_eq!(mixture.heat_capacity(&gases, &physics), (4.0 * 20.0 + 200.0) / 8.0);
+        assert_eq!(mixture.thermal_energy(&gases, &physics), 35.0 * 300.0);
+        assert_eq!(mixture.unscaled_thermal_energy(&gases, &physics), 280.0 * 300.0);
+        let empty = Mixture::new(2, 300.0, 2500.0);
+        assert_eq!(empty.heat_capacity(&gases, &physics), 0.0003);
+    }
+
+    #[test]
+    fn pressure_follows_the_ideal_gas_law() {
+        let physics = physics();
+        let mixture = Mixture { moles: vec![100.0, 0.0], temperature: 300.0, volume: 2500.0 };
+        assert!((mixture.pressure(&physics) - 100.0 * 8.314462618 * 300.0 / 2500.0).abs() < 1e-9);
+        assert_eq!(Mixture { volume: 0.0, ..mixture }.pressure(&physics), 0.0);
+    }
+
+    #[test]
+    fn properties_weigh_components_by_moles() {
+        let (gases, physics) = (gases(), physics());
+        let mixture = Mixture { moles: vec![3.0, 1.0], temperature: 300.0, volume: 2500.0 };
+        let properties = mixture.properties(&gases, &physics);
+        assert_eq!(properties.total_moles, 4.0);
+        assert_eq!(properties.mass, 3.0 * 32.0 + 120.0);
+        assert_eq!(properties.molar_mass, Some((3.0 * 32.0 + 120.0) / 4.0));
+        assert!((properties.heat_capacity_ratio.unwrap() - (3.0 * 1.4 + 1.7) / 4.0).abs() < 1e-12);
+        assert_eq!(properties.thermal_energy, properties.heat_capacity * 300.0);
+        let names: Vec<&str> = properties.components.iter().map(|component| component.gas.as_str()).collect();
+        assert_eq!(names, ["Oxygen", "Plasma"]);
+        assert_eq!(properties.components[0].fraction, 0.75);
+        assert!((properties.components[0].partial_pressure - properties.pressure * 0.75).abs() < 1e-9);
+
+        let oxygen_only = Mixture { moles: vec![2.0, 0.0], ..mixture };
+        assert_eq!(oxygen_only.properties(&gases, &physics).components.len(), 1);
+    }
+
+    #[test]
+    fn merge_weighs_temperature_by_heat_capacity_and_sums_moles() {
+        let (gases, physics) = (gases(), physics());
+        let mut receiver = Mixture { moles: vec![10.0, 0.0], temperature: 300.0, volume: 2500.0 };
+        let giver = Mixture { moles: vec![0.0, 1.0], temperature: 600.0, volume: 100.0 };
+        receiver.merge(&giver, &gases, &physics);
+        // 10 mol of oxygen and 1 mol of plasma hold 200 J/K each before scaling, so they meet halfway.
+        assert!((receiver.temperature - 450.0).abs() < 1e-9);
+        assert_eq!(receiver.moles, vec![10.0, 1.0]);
+        assert_eq!(receiver.volume, 2500.0);
+    }
+
+    #[test]
+    fn merge_keeps_the_temperature_within_the_minimum_delta() {
+        let (gases, physics) = (gases(), physics());
+        let mut receiver = Mixture { moles: vec![10.0, 0.0], temperature: 300.0, volume: 2500.0 };
+        let giver = Mixture { moles: vec![10.0, 0.0], temperature: 300.005, volume: 2500.0 };
+        receiver.merge(&giver, &gases, &physics);
+        assert_eq!(receiver.temperature, 300.0);
+        assert_eq!(receiver.moles, vec![20.0, 0.0]);
+
+        let mut short = Mixture { moles: vec![1.0], temperature: 300.0, volume: 2500.0 };
+        short.merge(&Mixture { moles: vec![1.0, 2.0], temperature: 300.0, volume: 2500.0 }, &gases, &physics);
+        assert_eq!(short.moles, vec![2.0, 2.0]);
+    }
+}
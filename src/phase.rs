@@ -0,0 +1,159 @@
+use crate::eval::sweep_values;
+use crate::mixture::{Mixture, Physics};
+use crate::simulation::Simulation;
+use crate::Gas;
+use std::collections::BTreeSet;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Parameter {
+    Temperature,
+    Pressure,
+    Ratio { gas: usize, other: usize },
+}
+
+impl Parameter {
+    pub fn label(&self, gases: &[Gas]) -> String {
+        let name = |index: usize| gases.get(index).map_or_else(|| format!("gas #{}", index), |gas| gas.name.clone());
+        match *self {
+            Parameter::Temperature => "Temperature (K)".to_string(),
+            Parameter::Pressure => "Pressure (kPa)".to_string(),
+            Parameter::Ratio { gas, other } => format!("{}:{} ratio", name(gas), name(other)),
+        }
+    }
+
+    // A ratio of a gas to itself would split its moles against themselves and leave nonsense behind.
+    pub fn validate(&self, gases: &[Gas]) -> Result<(), String> {
+        match *self {
+            Parameter::Ratio { gas, other } if gas == other => Err(format!("the {} needs two different gases", self.label(gases))),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn apply(&self, mixture: &mut Mixture, value: f64, physics: &Physics) {
+        match *self {
+            Parameter::Temperature => mixture.temperature = value,
+            Parameter::Pressure => {
+                let pressure = mixture.pressure(physics);
+                if pressure > 0.0 {
+                    mixture.moles.iter_mut().for_each(|moles| *moles *= value / pressure);
+                }
+            }
+            Parameter::Ratio { gas, other } => {
+                let total = mixture.moles[gas] + mixture.moles[other];
+                mixture.moles[gas] = total * value / (1.0 + value);
+                mixture.moles[other] = total / (1.0 + value);
+            }
+        }
+    }
+
+    // Temperature goes first because pressure depends on it, and pressure last so it scales the final composition.
+    fn order(&self) -> usize {
+        match self {
+            Parameter::Temperature => 0,
+            Parameter::Ratio { .. } => 1,
+            Parameter::Pressure => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Axis {
+    pub parameter: Parameter,
+    pub from: f64,
+    pub to: f64,
+    pub steps: usize,
+}
+
+impl Axis {
+    pub fn values(&self) -> Vec<f64> {
+        sweep_values(self.from, self.to, self.steps)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outcome {
+    pub energy_released: f64,
+    pub temperature_change: f64,
+    pub final_temperature: f64,
+    pub final_pressure: f64,
+    pub reactions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PhaseMap {
+    pub x: Vec<f64>,
+    pub y: Vec<f64>,
+    pub outcomes: Vec<Outcome>,
+    pub warnings: Vec<String>,
+}
+
+impl PhaseMap {
+    pub fn get(&self, x: usize, y: usize) -> Option<&Outcome> {
+        if x >= self.x.len() {
+            return None;
+        }
+        self.outcomes.get(y * self.x.len() + x)
+    }
+}
+
+pub fn phase_map(simulation: &Simulation, base: &Mixture, x: Axis, y: Axis, ticks: usize) -> PhaseMap {
+    phase_map_until(simulation, base, x, y, ticks, &|| false).unwrap_or_default()
+}
+
+// Same as phase_map, but gives up between cells as soon as `cancelled` returns true.
+pub fn phase_map_until(simulation: &Simulation, base: &Mixture, x: Axis, y: Axis, ticks: usize, cancelled: &dyn Fn() -> bool) -> Option<PhaseMap> {
+    let (gases, physics) = (simulation.gases(), simulation.physics());
+    let mut map = PhaseMap {
+        x: x.values(),
+        y: y.values(),
+        ..PhaseMap::default()
+    };
+    let mut warnings = BTreeSet::new();
+    for &y_value in &map.y {
+        for &x_value in &map.x {
+            if cancelled() {
+                return None;
+            }
+            let mut mixture = base.clone();
+            let mut parameters = [(x.parameter, x_value), (y.parameter, y_value)];
+            parameters.sort_by_key(|(parameter, _)| parameter.order());
+            for (parameter, value) in parameters {
+                parameter.apply(&mut mixture, value, physics);
+            }
+
+            let initial_temperature = mixture.temperature;
+            let initial_energy = mixture.thermal_energy(gases, physics);
+            let mut reactions: Vec<String> = Vec::new();
+            for _ in 0..ticks {
+                for reaction in simulation.tick(&mut mixture, &mut warnings) {
+                    if !reactions.contains(&reaction) {
+                        reactions.push(reaction);
+                    }
+                }
+            }
+            map.outcomes.push(Outcome {
+                energy_released: mixture.thermal_energy(gases, physics) - initial_energy,
+                temperature_change: mixture.temperature - initial_temperature,
+                final_temperature: mixture.temperature,
+                final_pressure: mixture.pressure(physics),
+                reactions,
+            });
+        }
+    }
+    map.warnings = warnings.into_iter().collect();
+    Some(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gas::parse_gases;
+
+    #[test]
+    fn ratio_of_a_gas_to_itself_is_rejected() {
+        let gases = parse_gases("- type: gas\n  id: 0\n  name: Oxygen\n- type: gas\n  id: 1\n  name: Plasma\n").unwrap();
+        assert_eq!(Parameter::Ratio { gas: 0, other: 1 }.validate(&gases), Ok(()));
+        assert_eq!(Parameter::Ratio { gas: 1, other: 1 }.validate(&gases), Err("the Plasma:Plasma ratio needs two different gases".to_string()));
+        assert_eq!(Parameter::Temperature.validate(&gases), Ok(()));
+    }
+}
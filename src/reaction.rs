@@ -0,0 +1,238 @@
+use serde::Deserialize;
+use serde_yaml::{Mapping, Value};
+use std::fmt;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Reaction {
+    pub id: String,
+    pub priority: Option<i32>,
+    #[serde(rename = "minimumTemperature")]
+    pub minimum_temperature: Option<f64>,
+    #[serde(rename = "maximumTemperature")]
+    pub maximum_temperature: Option<f64>,
+    #[serde(rename = "minimumEnergy")]
+    pub minimum_energy: Option<f64>,
+    #[serde(rename = "minimumRequirements")]
+    pub minimum_requirements: Vec<f64>,
+    pub effects: Vec<Effect>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Requirement {
+    pub gas: String,
+    pub moles: f64,
+}
+
+impl Reaction {
+    pub fn requirements(&self, gases: &[String]) -> Vec<Requirement> {
+        self.minimum_requirements
+            .iter()
+            .enumerate()
+            .filter(|(_, moles)| **moles > 0.0)
+            .map(|(index, moles)| Requirement {
+                gas: gases.get(index).cloned().unwrap_or_else(|| format!("gas #{}", index)),
+                moles: *moles,
+            })
+            .collect()
+    }
+}
+
+pub fn requirement_warnings(reactions: &[Reaction], gases: &[String]) -> Vec<String> {
+    reactions
+        .iter()
+        .filter(|reaction| reaction.minimum_requirements.len() != gases.len())
+        .map(|reaction| {
+            format!(
+                "{} lists {} minimum requirements but {} gases are known",
+                reaction.id,
+                reaction.minimum_requirements.len(),
+                gases.len()
+            )
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Effect {
+    PlasmaFire,
+    TritiumFire,
+    FrezonProduction,
+    FrezonCoolant,
+    N2ODecomposition,
+    MiasmicSubsumation,
+    AmmoniaOxygen,
+    WaterVapor {
+        reagent: Option<String>,
+        gas: Option<u64>,
+        moles_per_unit: Option<f64>,
+        puddle_prototype: Option<String>,
+    },
+    Unknown {
+        tag: Option<String>,
+        value: Value,
+    },
+}
+
+impl Effect {
+    pub fn parse(effect: Value) -> Self {
+        let (tag, value) = match effect {
+            Value::Tagged(tagged) => (Some(tagged.tag.to_string()), tagged.value),
+            value => (None, value),
+        };
+        let kind = tag.as_deref().map(|tag| tag.trim_start_matches('!').trim_start_matches("type:"));
+        let empty = Mapping::new();
+        let parameters = match &value {
+            Value::Mapping(parameters) => parameters,
+            Value::Null => &empty,
+            _ => return Effect::Unknown { tag, value },
+        };
+        let get = |key: &str| parameters.get(key);
+        match kind {
+            Some("WaterVaporReaction") => Effect::WaterVapor {
+                reagent: get("reagent").and_then(Value::as_str).map(str::to_string),
+                gas: get("gas").and_then(Value::as_u64),
+                moles_per_unit: get("molesPerUnit").and_then(Value::as_f64),
+                puddle_prototype: get("puddlePrototype").and_then(Value::as_str).map(str::to_string),
+            },
+            // Parameters on the reactions below are not known, so keep them visible as raw YAML.
+            _ if !parameters.is_empty() => Effect::Unknown { tag, value },
+            Some("PlasmaFireReaction") => Effect::PlasmaFire,
+            Some("TritiumFireReaction") => Effect::TritiumFire,
+            Some("FrezonProductionReaction") => Effect::FrezonProduction,
+            Some("FrezonCoolantReaction") => Effect::FrezonCoolant,
+            Some("N2ODecompositionReaction") => Effect::N2ODecomposition,
+            Some("MiasmicSubsumationReaction") => Effect::MiasmicSubsumation,
+            Some("AmmoniaOxygenReaction") => Effect::AmmoniaOxygen,
+            _ => Effect::Unknown { tag, value },
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Effect {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Value::deserialize(deserializer).map(Effect::parse)
+    }
+}
+
+pub fn parse_reactions(text: &str) -> Result<Vec<Reaction>, serde_yaml::Error> {
+    let docs: Vec<Value> = serde_yaml::from_str(text)?;
+    let mut reactions = Vec::new();
+    for doc in docs {
+        if doc.get("type").and_then(Value::as_str).is_some_and(|kind| kind != "gasReaction") {
+            continue;
+        }
+        if let Value::Mapping(mut map) = doc {
+            let id = map.get(Value::String("id".to_string())).and_then(Value::as_str).unwrap_or("n/a").to_string();
+            let priority = map.get(Value::String("priority".to_string())).and_then(Value::as_i64).map(|v| v as i32);
+            let minimum_temperature = map.get(Value::String("minimumTemperature".to_string())).and_then(Value::as_f64);
+            let maximum_temperature = map.get(Value::String("maximumTemperature".to_string())).and_then(Value::as_f64);
+            let minimum_energy = map.get(Value::String("minimumEnergy".to_string())).and_then(Value::as_f64);
+            let minimum_requirements = map.get(Value::String("minimumRequirements".to_string()))
+                .and_then(Value::as_sequence)
+                .map(|seq| seq.iter().filter_map(Value::as_f64).collect())
+                .unwrap_or_default();
+
+            let effects = if let Some(Value::Sequence(effects)) = map.remove(Value::String("effects".to_string())) {
+                parse_effects(effects)
+            } else {
+                Vec::new()
+            };
+
+            reactions.push(Reaction {
+                id,
+                priority,
+                minimum_temperature,
+                maximum_temperature,
+                minimum_energy,
+                minimum_requirements,
+                effects,
+            });
+        } else {
+            return Err(serde::de::Error::custom("reaction is not a mapping"));
+        }
+    }
+    Ok(reactions)
+}
+
+pub fn parse_effects(effects: Vec<Value>) -> Vec<Effect> {
+    effects.into_iter().map(Effect::parse).collect()
+}
+
+impl fmt::Display for Requirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ≥ {} mol", self.gas, self.moles)
+    }
+}
+
+impl Effect {
+    // Like Display, but with the condensing gas named from the gas list instead of its index.
+    pub fn describe(&self, gases: &[String]) -> String {
+        let Effect::WaterVapor { reagent, gas, moles_per_unit, puddle_prototype } = self else {
+            return self.to_string();
+        };
+        let gas = match gas {
+            Some(gas) => gases.get(*gas as usize).cloned().unwrap_or_else(|| format!("gas #{}", gas)),
+            None => "gas #?".to_string(),
+        };
+        let mut text = format!("Condenses {} into {}", gas, reagent.as_deref().unwrap_or("?"));
+        if let Some(moles_per_unit) = moles_per_unit {
+            text += &format!(" ({} mol per unit)", moles_per_unit);
+        }
+        if let Some(puddle_prototype) = puddle_prototype {
+            text += &format!(" as {}", puddle_prototype);
+        }
+        text
+    }
+}
+
+impl fmt::Display for Effect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Effect::PlasmaFire => f.write_str("Plasma fire"),
+            Effect::TritiumFire => f.write_str("Tritium fire"),
+            Effect::FrezonProduction => f.write_str("Frezon production"),
+            Effect::FrezonCoolant => f.write_str("Frezon coolant"),
+            Effect::N2ODecomposition => f.write_str("N2O decomposition"),
+            Effect::MiasmicSubsumation => f.write_str("Miasmic subsumation"),
+            Effect::AmmoniaOxygen => f.write_str("Ammonia/oxygen reaction"),
+            Effect::WaterVapor { .. } => f.write_str(&self.describe(&[])),
+            Effect::Unknown { tag, value } => {
+                let parameters = serde_json::to_string(value).unwrap_or_else(|_| serde_yaml::to_string(value).unwrap_or_default());
+                write!(f, "{} {}", tag.as_deref().unwrap_or("(untagged)"), parameters)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn water_vapor_effect_names_its_gas() {
+        let reactions = parse_reactions("- type: gasReaction\n  id: WaterVaporPuddle\n  effects:\n  - !type:WaterVaporReaction\n    reagent: Water\n    gas: 1\n    molesPerUnit: 1\n").unwrap();
+        let effect = &reactions[0].effects[0];
+        let gases = ["Oxygen".to_string(), "WaterVapor".to_string()];
+        assert_eq!(effect.describe(&gases), "Condenses WaterVapor into Water (1 mol per unit)");
+        assert_eq!(effect.describe(&gases[..1]), "Condenses gas #1 into Water (1 mol per unit)");
+        assert_eq!(effect.to_string(), "Condenses gas #1 into Water (1 mol per unit)");
+    }
+
+    #[test]
+    fn warns_when_requirements_and_gases_differ_in_length() {
+        let reactions = parse_reactions(
+            "- type: gasReaction\n  id: Short\n  minimumRequirements: [1]\n\
+             - type: gasReaction\n  id: Exact\n  minimumRequirements: [1, 0]\n\
+             - type: gasReaction\n  id: Long\n  minimumRequirements: [1, 0, 0]\n",
+        )
+        .unwrap();
+        let gases = ["Oxygen".to_string(), "Plasma".to_string()];
+        assert_eq!(
+            requirement_warnings(&reactions, &gases),
+            vec![
+                "Short lists 1 minimum requirements but 2 gases are known".to_string(),
+                "Long lists 3 minimum requirements but 2 gases are known".to_string(),
+            ]
+        );
+    }
+}
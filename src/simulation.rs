@@ -0,0 +1,367 @@
+use crate::mixture::{Mixture, Physics};
+use crate::reaction::Effect;
+use crate::{Gas, Reaction};
+use std::collections::{BTreeSet, HashMap};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub tick: usize,
+    pub moles: Vec<f64>,
+    pub temperature: f64,
+    pub pressure: f64,
+    pub reactions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Run {
+    pub steps: Vec<Step>,
+    pub warnings: Vec<String>,
+}
+
+pub struct Simulation<'a> {
+    gases: &'a [Gas],
+    names: &'a [String],
+    reactions: Vec<&'a Reaction>,
+    physics: Physics,
+    constants: HashMap<String, f64>,
+    slots: Vec<Option<usize>>,
+    pub tile: bool,
+}
+
+impl<'a> Simulation<'a> {
+    pub fn new(gases: &'a [Gas], names: &'a [String], reactions: &'a [Reaction], physics: Physics, evaluated: &HashMap<String, Result<String, String>>) -> Self {
+        // Reactions run from the highest priority down, like AtmosphereSystem.React.
+        let mut reactions: Vec<&Reaction> = reactions.iter().collect();
+        reactions.sort_by_key(|reaction| -reaction.priority.unwrap_or(0));
+        let constants = evaluated
+            .iter()
+            .filter_map(|(name, value)| Some((name.clone(), value.as_ref().ok()?.parse().ok()?)))
+            .collect();
+        let has_ids = gases.iter().any(|gas| gas.id.is_some());
+        let slots = (0..names.len().max(gases.len()))
+            .map(|index| {
+                if has_ids {
+                    gases.iter().position(|gas| gas.id.as_deref() == Some(index.to_string().as_str()))
+                } else {
+                    (index < gases.len()).then_some(index)
+                }
+            })
+            .collect();
+        Self {
+            gases,
+            names,
+            reactions,
+            physics,
+            constants,
+            slots,
+            tile: true,
+        }
+    }
+
+    pub fn gases(&self) -> &'a [Gas] {
+        self.gases
+    }
+
+    pub fn physics(&self) -> &Physics {
+        &self.physics
+    }
+
+    pub fn run(&self, mut mixture: Mixture, ticks: usize) -> Run {
+        let mut warnings = BTreeSet::new();
+        let mut steps = vec![self.step(0, &mixture, Vec::new())];
+        for tick in 1..=ticks {
+            let reactions = self.tick(&mut mixture, &mut warnings);
+            steps.push(self.step(tick, &mixture, reactions));
+        }
+        Run {
+            steps,
+            warnings: warnings.into_iter().collect(),
+        }
+    }
+
+    pub fn tick(&self, mixture: &mut Mixture, warnings: &mut BTreeSet<String>) -> Vec<String> {
+        mixture.moles.resize(self.gases.len(), 0.0);
+        let mut reacted = Vec::new();
+        // Like AtmosphereSystem.React, every reaction is checked against the mixture as it was at the
+        // start of the tick, not as the reactions before it left it.
+        let temperature = mixture.temperature;
+        let energy = mixture.unscaled_thermal_energy(self.gases, &self.physics);
+        for reaction in &self.reactions {
+            let minimum = reaction.minimum_temperature.unwrap_or_else(|| self.constants.get("TCMB").copied().unwrap_or(0.0));
+            if energy < reaction.minimum_energy.unwrap_or(0.0) || temperature < minimum || temperature > reaction.maximum_temperature.unwrap_or(f64::MAX) {
+                continue;
+            }
+            let missing = reaction.minimum_requirements.iter().enumerate().any(|(index, required)| {
+                let moles = self.slots.get(index).copied().flatten().map_or(0.0, |slot| mixture.moles[slot]);
+                moles < *required
+            });
+            if missing {
+                continue;
+            }
+            let mut reacting = false;
+            for effect in &reaction.effects {
+                match self.apply(effect, mixture) {
+                    Ok(result) => reacting |= result,
+                    Err(err) => {
+                        warnings.insert(format!("{}: {}", reaction.id, err));
+                    }
+                }
+            }
+            if reacting {
+                reacted.push(reaction.id.clone());
+            }
+        }
+        reacted
+    }
+
+    fn step(&self, tick: usize, mixture: &Mixture, reactions: Vec<String>) -> Step {
+        Step {
+            tick,
+            moles: mixture.moles.clone(),
+            temperature: mixture.temperature,
+            pressure: mixture.pressure(&self.physics),
+            reactions,
+        }
+    }
+
+    fn constant(&self, name: &str) -> Result<f64, String> {
+        self.constants.get(name).copied().ok_or_else(|| format!("constant {} is missing", name))
+    }
+
+    fn slot(&self, name: &str) -> Result<usize, String> {
+        self.names
+            .iter()
+            .position(|gas| gas == name)
+            .and_then(|index| self.slots.get(index).copied().flatten())
+            .ok_or_else(|| format!("gas {} is not loaded", name))
+    }
+
+    fn heat_capacity(&self, mixture: &Mixture) -> f64 {
+        mixture.heat_capacity(self.gases, &self.physics)
+    }
+
+    // Heats (or cools) the mixture by the released energy, already divided by HeatScale.
+    fn release(&self, mixture: &mut Mixture, temperature: f64, old_capacity: f64, energy: f64) {
+        let new_capacity = self.heat_capacity(mixture);
+        if new_capacity > self.physics.minimum_heat_capacity {
+            mixture.temperature = (temperature * old_capacity + energy) / new_capacity;
+        }
+    }
+
+    fn apply(&self, effect: &Effect, mixture: &mut Mixture) -> Result<bool, String> {
+        match effect {
+            Effect::PlasmaFire => self.plasma_fire(mixture),
+            Effect::TritiumFire => self.tritium_fire(mixture),
+            Effect::FrezonCoolant => self.frezon_coolant(mixture),
+            Effect::FrezonProduction => self.frezon_production(mixture),
+            Effect::N2ODecomposition => self.n2o_decomposition(mixture),
+            Effect::AmmoniaOxygen => self.ammonia_oxygen(mixture),
+            Effect::WaterVapor { gas, moles_per_unit, .. } => {
+                if !self.tile {
+                    return Ok(false);
+                }
+                let slot = gas
+                    .and_then(|gas| self.slots.get(gas as usize).copied().flatten())
+                    .ok_or("the condensing gas is not loaded")?;
+                let moles_per_unit = moles_per_unit.unwrap_or(1.0);
+                if mixture.moles[slot] < moles_per_unit {
+                    return Ok(false);
+                }
+                adjust(mixture, slot, -moles_per_unit);
+                Ok(true)
+            }
+            Effect::MiasmicSubsumation | Effect::Unknown { .. } => Err(format!("{} is not simulated", effect)),
+        }
+    }
+
+    fn plasma_fire(&self, mixture: &mut Mixture) -> Result<bool, String> {
+        let (oxygen, plasma) = (self.slot("Oxygen")?, self.slot("Plasma")?);
+        let (tritium, carbon_dioxide) = (self.slot("Tritium")?, self.slot("CarbonDioxide")?);
+        let minimum_burn = self.constant("PlasmaMinimumBurnTemperature")?;
+        let upper = self.constant("PlasmaUpperTemperature")?;
+        let old_capacity = self.heat_capacity(mixture);
+        let temperature = mixture.temperature;
+
+        let scale = if temperature > upper { 1.0 } else { (temperature - minimum_burn) / (upper - minimum_burn) };
+        if scale <= 0.0 {
+            return Ok(false);
+        }
+        let oxygen_burn_rate = self.constant("OxygenBurnRateBase")? - scale;
+        let (initial_oxygen, initial_plasma) = (mixture.moles[oxygen], mixture.moles[plasma]);
+        let ends = self.constant("SuperSaturationEnds")?;
+        let supersaturation = ((initial_oxygen / initial_plasma - ends) / (self.constant("SuperSaturationThreshold")? - ends)).clamp(0.0, 1.0);
+        let fullburn = self.constant("PlasmaOxygenFullburn")?;
+        let delta = self.constant("PlasmaBurnRateDelta")?;
+        let mut burn_rate = if initial_oxygen > initial_plasma * fullburn {
+            initial_plasma * scale / delta
+        } else {
+            scale * (initial_oxygen / fullburn) / delta
+        };
+        if burn_rate <= self.physics.minimum_heat_capacity {
+            return Ok(false);
+        }
+        burn_rate = burn_rate.min(initial_plasma.min(initial_oxygen / oxygen_burn_rate));
+        mixture.moles[plasma] = initial_plasma - burn_rate;
+        mixture.moles[oxygen] = initial_oxygen - burn_rate * oxygen_burn_rate;
+        adjust(mixture, tritium, burn_rate * supersaturation);
+        adjust(mixture, carbon_dioxide, burn_rate * (1.0 - supersaturation));
+
+        let energy = self.constant("FirePlasmaEnergyReleased")? * burn_rate / self.physics.heat_scale;
+        if energy > 0.0 {
+            self.release(mixture, temperature, old_capacity, energy);
+        }
+        Ok(true)
+    }
+
+    fn tritium_fire(&self, mixture: &mut Mixture) -> Result<bool, String> {
+        let (oxygen, tritium, water_vapor) = (self.slot("Oxygen")?, self.slot("Tritium")?, self.slot("WaterVapor")?);
+        let hydrogen_energy = self.constant("FireHydrogenEnergyReleased")?;
+        let old_capacity = self.heat_capacity(mixture);
+        let temperature = mixture.temperature;
+        let initial_tritium = mixture.moles[tritium];
+        let mut energy = 0.0;
+
+        let burned = if mixture.moles[oxygen] < initial_tritium
+            || self.constant("MinimumTritiumOxyburnEnergy")? > temperature * old_capacity * self.physics.heat_scale
+        {
+            let burned = (mixture.moles[oxygen] / self.constant("TritiumBurnOxyFactor")?).min(initial_tritium);
+            adjust(mixture, tritium, -burned);
+            burned
+        } else {
+            let factor = self.constant("TritiumBurnTritFactor")?;
+            mixture.moles[tritium] *= 1.0 - 1.0 / factor;
+            let remaining = mixture.moles[tritium];
+            adjust(mixture, oxygen, -remaining);
+            energy += hydrogen_energy * initial_tritium * (factor - 1.0);
+            initial_tritium
+        };
+        if burned > 0.0 {
+            energy += hydrogen_energy * burned;
+            adjust(mixture, water_vapor, burned);
+        }
+        let energy = energy / self.physics.heat_scale;
+        if energy > 0.0 {
+            self.release(mixture, temperature, old_capacity, energy);
+        }
+        Ok(burned > 0.0)
+    }
+
+    fn frezon_coolant(&self, mixture: &mut Mixture) -> Result<bool, String> {
+        let (nitrogen, frezon, nitrous_oxide) = (self.slot("Nitrogen")?, self.slot("Frezon")?, self.slot("NitrousOxide")?);
+        let old_capacity = self.heat_capacity(mixture);
+        let temperature = mixture.temperature;
+        let lower = self.constant("FrezonCoolLowerTemperature")?;
+        let mut scale = (temperature - lower) / (self.constant("FrezonCoolMidTemperature")? - lower);
+        let mut energy_modifier = 1.0;
+        if scale > 1.0 {
+            energy_modifier = scale.min(self.constant("FrezonCoolMaximumEnergyModifier")?);
+            scale = 1.0;
+        }
+        if scale <= 0.0 {
+            return Ok(false);
+        }
+        let (initial_nitrogen, initial_frezon) = (mixture.moles[nitrogen], mixture.moles[frezon]);
+        let burn_rate = initial_frezon * scale / self.constant("FrezonCoolRateModifier")?;
+        let mut energy = 0.0;
+        if burn_rate > self.physics.minimum_heat_capacity {
+            let nitrogen_used = (burn_rate * self.constant("FrezonNitrogenCoolRatio")?).min(initial_nitrogen);
+            let frezon_used = burn_rate.min(initial_frezon);
+            adjust(mixture, nitrogen, -nitrogen_used);
+            adjust(mixture, frezon, -frezon_used);
+            adjust(mixture, nitrous_oxide, nitrogen_used + frezon_used);
+            energy = burn_rate * self.constant("FrezonCoolEnergyReleased")? * energy_modifier;
+        }
+        let energy = energy / self.physics.heat_scale;
+        if energy >= 0.0 {
+            return Ok(false);
+        }
+        self.release(mixture, temperature, old_capacity, energy);
+        Ok(true)
+    }
+
+    fn frezon_production(&self, mixture: &mut Mixture) -> Result<bool, String> {
+        let (nitrogen, oxygen) = (self.slot("Nitrogen")?, self.slot("Oxygen")?);
+        let (tritium, frezon) = (self.slot("Tritium")?, self.slot("Frezon")?);
+        let efficiency = mixture.temperature / self.constant("FrezonProductionMaxEfficiencyTemperature")?;
+        let loss = 1.0 - efficiency;
+        let trit_ratio = self.constant("FrezonProductionTritRatio")?;
+        let conversion_rate = self.constant("FrezonProductionConversionRate")?;
+
+        let catalyst_limit = mixture.moles[nitrogen] * (self.constant("FrezonProductionNitrogenRatio")? / efficiency);
+        let oxygen_limit = mixture.moles[oxygen].min(catalyst_limit) / trit_ratio;
+        let tritium_burned = oxygen_limit.min(mixture.moles[tritium]);
+        let oxygen_conversion = tritium_burned * trit_ratio / conversion_rate;
+        let tritium_conversion = tritium_burned / conversion_rate;
+        let total = oxygen_conversion + tritium_conversion;
+        adjust(mixture, oxygen, -oxygen_conversion);
+        adjust(mixture, tritium, -tritium_conversion);
+        adjust(mixture, frezon, total * efficiency);
+        adjust(mixture, nitrogen, total * loss);
+        Ok(true)
+    }
+
+    fn n2o_decomposition(&self, mixture: &mut Mixture) -> Result<bool, String> {
+        let (nitrous_oxide, nitrogen, oxygen) = (self.slot("NitrousOxide")?, self.slot("Nitrogen")?, self.slot("Oxygen")?);
+        let initial = mixture.moles[nitrous_oxide];
+        let burned = initial / self.constant("N2ODecompositionRate")?;
+        if burned <= 0.0 || initial - burned < 0.0 {
+            return Ok(false);
+        }
+        adjust(mixture, nitrous_oxide, -burned);
+        adjust(mixture, nitrogen, burned);
+        adjust(mixture, oxygen, burned / 2.0);
+        Ok(true)
+    }
+
+    fn ammonia_oxygen(&self, mixture: &mut Mixture) -> Result<bool, String> {
+        let (ammonia, oxygen) = (self.slot("Ammonia")?, self.slot("Oxygen")?);
+        let (nitrous_oxide, water_vapor) = (self.slot("NitrousOxide")?, self.slot("WaterVapor")?);
+        let (initial_ammonia, initial_oxygen, total) = (mixture.moles[ammonia], mixture.moles[oxygen], mixture.total_moles());
+        let rate = (initial_ammonia / total).powi(2) * (initial_oxygen / total).powi(2);
+        let delta = initial_ammonia / self.constant("AmmoniaOxygenReactionRate")? * 2.0 * rate;
+        if delta <= 0.0 || initial_ammonia - delta < 0.0 {
+            return Ok(false);
+        }
+        adjust(mixture, ammonia, -delta);
+        adjust(mixture, oxygen, -delta);
+        adjust(mixture, nitrous_oxide, delta / 2.0);
+        adjust(mixture, water_vapor, delta * 1.5);
+        Ok(true)
+    }
+}
+
+fn adjust(mixture: &mut Mixture, slot: usize, moles: f64) {
+    mixture.moles[slot] = (mixture.moles[slot] + moles).max(0.0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gas::parse_gases;
+    use crate::reaction::parse_reactions;
+
+    #[test]
+    fn reactions_below_their_minimum_energy_do_not_run() {
+        let gases = parse_gases("- type: gas\n  id: 0\n  name: WaterVapor\n  specificHeat: 20\n").unwrap();
+        let names = vec!["WaterVapor".to_string()];
+        let reactions = parse_reactions(
+            "- type: gasReaction\n  id: Hot\n  minimumEnergy: 100000\n  minimumRequirements: [1]\n  effects:\n  - !type:WaterVaporReaction\n    gas: 0\n    molesPerUnit: 1\n\
+             - type: gasReaction\n  id: Warm\n  minimumEnergy: 10000\n  minimumRequirements: [1]\n  effects:\n  - !type:WaterVaporReaction\n    gas: 0\n    molesPerUnit: 1\n",
+        )
+        .unwrap();
+        let physics = Physics {
+            r: 8.314462618,
+            heat_scale: 8.0,
+            minimum_heat_capacity: 0.0003,
+            minimum_temperature_delta: 0.01,
+            room_temperature: 293.15,
+            cell_volume: 2500.0,
+        };
+        let simulation = Simulation::new(&gases, &names, &reactions, physics, &HashMap::new());
+        // 10 mol at 20 J/K/mol and 300 K holds 60 kJ; dividing by HeatScale first would leave 7.5 kJ.
+        let mut mixture = Mixture { moles: vec![10.0], temperature: 300.0, volume: 2500.0 };
+        let reacted = simulation.tick(&mut mixture, &mut BTreeSet::new());
+        assert_eq!(reacted, vec!["Warm".to_string()]);
+        assert_eq!(mixture.moles, vec![9.0]);
+    }
+}
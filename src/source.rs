@@ -0,0 +1,193 @@
+use crate::cache::{self, format_age};
+use crate::error::{Error, Result};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+pub const CONSTANTS_PATH: &str = "Content.Shared/Atmos/Atmospherics.cs";
+pub const PROTOTYPES_DIR: &str = "Resources/Prototypes/Atmospherics";
+pub const GASES_FILE: &str = "gases.yml";
+pub const REACTIONS_FILE: &str = "reactions.yml";
+pub const TEG_PATH: &str = "Resources/Prototypes/Entities/Structures/Power/Generation/teg.yml";
+
+pub const DEFAULT_OWNER: &str = "space-wizards";
+pub const DEFAULT_REPO: &str = "space-station-14";
+pub const DEFAULT_REF: &str = "master";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DataSource {
+    GitHub { owner: String, repo: String, git_ref: String },
+    Url(String),
+    Local(PathBuf),
+}
+
+impl Default for DataSource {
+    fn default() -> Self {
+        DataSource::github(DEFAULT_OWNER, DEFAULT_REPO, DEFAULT_REF)
+    }
+}
+
+impl DataSource {
+    pub fn github(owner: &str, repo: &str, git_ref: &str) -> Self {
+        DataSource::GitHub {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            git_ref: git_ref.to_string(),
+        }
+    }
+
+    pub fn url(base_url: &str) -> Result<Self> {
+        let base_url = base_url.trim().trim_end_matches('/');
+        if base_url.starts_with("http://") || base_url.starts_with("https://") {
+            Ok(DataSource::Url(base_url.to_string()))
+        } else {
+            Err(Error::InvalidSource(format!("{} is not an http(s) URL", base_url)))
+        }
+    }
+
+    pub fn parse(spec: &str) -> Result<Self> {
+        let spec = spec.trim();
+        if spec.starts_with("http://") || spec.starts_with("https://") {
+            return DataSource::url(spec);
+        }
+        if Path::new(spec).is_dir() {
+            return DataSource::local(spec);
+        }
+        let (repository, git_ref) = spec.split_once('@').unwrap_or((spec, DEFAULT_REF));
+        if repository.is_empty() && !git_ref.is_empty() {
+            return Ok(DataSource::github(DEFAULT_OWNER, DEFAULT_REPO, git_ref));
+        }
+        match repository.split_once('/') {
+            Some((owner, repo)) if !owner.is_empty() && !repo.is_empty() && !repo.contains('/') && !git_ref.is_empty() => {
+                Ok(DataSource::github(owner, repo, git_ref))
+            }
+            _ => Err(Error::InvalidSource(format!("{} is not a URL, a local checkout or an owner/repo[@ref] specification", spec))),
+        }
+    }
+
+    pub fn local(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        locate_checkout(path)
+            .map(DataSource::Local)
+            .ok_or_else(|| Error::InvalidSource(format!("{} is not inside a Space Station 14 checkout (no {} found)", path.display(), CONSTANTS_PATH)))
+    }
+
+    pub fn base_url(&self) -> Option<String> {
+        match self {
+            DataSource::GitHub { owner, repo, git_ref } => Some(format!("https://raw.githubusercontent.com/{}/{}/{}", owner, repo, git_ref)),
+            DataSource::Url(base_url) => Some(base_url.clone()),
+            DataSource::Local(_) => None,
+        }
+    }
+
+    pub fn constants_text(&self) -> Result<(SourceFile, Origin)> {
+        self.file_text(CONSTANTS_PATH)
+    }
+
+    pub fn file_text(&self, path: &str) -> Result<(SourceFile, Origin)> {
+        let mut origin = Origin::new(self.clone());
+        let file = match self {
+            DataSource::Local(root) => read_file(&root.join(path))?,
+            _ => origin.record(self.remote_url(path))?,
+        };
+        Ok((file, origin))
+    }
+
+    pub fn prototype_texts(&self, file: &str) -> Result<(Vec<SourceFile>, Origin)> {
+        let mut origin = Origin::new(self.clone());
+        let files = match self {
+            DataSource::Local(root) => prototype_files(&root.join(PROTOTYPES_DIR))?
+                .iter()
+                .map(|path| read_file(path))
+                .collect::<Result<_>>()?,
+            _ => vec![origin.record(self.remote_url(&format!("{}/{}", PROTOTYPES_DIR, file)))?],
+        };
+        Ok((files, origin))
+    }
+
+    pub fn line_url(&self, path: &str, line: usize) -> Option<String> {
+        match self {
+            DataSource::GitHub { owner, repo, git_ref } => Some(format!("https://github.com/{}/{}/blob/{}/{}#L{}", owner, repo, git_ref, path, line)),
+            DataSource::Url(_) => None,
+            DataSource::Local(root) => Some(format!("{}:{}", root.join(path).display(), line)),
+        }
+    }
+
+    fn remote_url(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url().unwrap_or_default(), path)
+    }
+}
+
+impl fmt::Display for DataSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataSource::GitHub { owner, repo, git_ref } => write!(f, "{}/{}@{}", owner, repo, git_ref),
+            DataSource::Url(base_url) => write!(f, "{}", base_url),
+            DataSource::Local(root) => write!(f, "{}", root.display()),
+        }
+    }
+}
+
+pub struct SourceFile {
+    pub name: String,
+    pub text: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Origin {
+    pub source: DataSource,
+    pub fetched_at: Option<SystemTime>,
+    // Set when a file came from the cache because fetching it failed, with the reason.
+    pub offline: Option<String>,
+}
+
+impl Origin {
+    pub fn new(source: DataSource) -> Self {
+        Self { source, fetched_at: None, offline: None }
+    }
+
+    fn record(&mut self, url: String) -> Result<SourceFile> {
+        let fetched = cache::fetch(&url)?;
+        self.fetched_at = Some(self.fetched_at.map_or(fetched.fetched_at, |time| time.min(fetched.fetched_at)));
+        if self.offline.is_none() {
+            self.offline = fetched.offline;
+        }
+        Ok(SourceFile { name: url, text: fetched.text })
+    }
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)?;
+        if let Some(fetched_at) = self.fetched_at {
+            write!(f, ", fetched {}", format_age(fetched_at))?;
+        }
+        if self.offline.is_some() {
+            write!(f, " (offline, cached copy)")?;
+        }
+        Ok(())
+    }
+}
+
+pub fn locate_checkout(path: &Path) -> Option<PathBuf> {
+    let path = path.canonicalize().ok()?;
+    path.ancestors()
+        .find(|dir| dir.join(CONSTANTS_PATH).is_file())
+        .map(Path::to_path_buf)
+}
+
+fn prototype_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let entries = fs::read_dir(dir).map_err(|source| Error::Io { path: dir.to_path_buf(), source })?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "yml"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+fn read_file(path: &Path) -> Result<SourceFile> {
+    let text = fs::read_to_string(path).map_err(|source| Error::Io { path: path.to_path_buf(), source })?;
+    Ok(SourceFile { name: path.display().to_string(), text })
+}
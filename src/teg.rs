@@ -0,0 +1,131 @@
+use crate::mixture::{Mixture, Physics};
+use crate::Gas;
+use serde_yaml::Value;
+
+// TegGeneratorComponent defaults, used when the prototype does not override them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Generator {
+    pub thermal_efficiency: f64,
+    pub power_factor: f64,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Self {
+            thermal_efficiency: 0.65,
+            power_factor: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transfer {
+    pub heat_transferred: f64,
+    pub carnot_efficiency: f64,
+    pub efficiency: f64,
+    pub electrical_energy: f64,
+    pub hot_outlet_temperature: f64,
+    pub cold_outlet_temperature: f64,
+}
+
+impl Generator {
+    // Same as TegSystem: the two circulated mixtures would settle at a common temperature, and the heat that takes is
+    // turned into electricity at the Carnot efficiency scaled by ThermalEfficiency; the rest warms the cold side.
+    pub fn transfer(&self, hot: &Mixture, cold: &Mixture, gases: &[Gas], physics: &Physics) -> Option<Transfer> {
+        if hot.pressure(physics) <= 0.0 || cold.pressure(physics) <= 0.0 || hot.temperature <= cold.temperature {
+            return None;
+        }
+        let (hot_capacity, cold_capacity) = (hot.heat_capacity(gases, physics), cold.heat_capacity(gases, physics));
+        if hot_capacity <= 0.0 || cold_capacity <= 0.0 {
+            return None;
+        }
+        let equilibrium_temperature = (hot.temperature * hot_capacity + cold.temperature * cold_capacity) / (hot_capacity + cold_capacity);
+        let heat_transferred = (hot.temperature - equilibrium_temperature) * hot_capacity;
+        let carnot_efficiency = 1.0 - cold.temperature / hot.temperature;
+        let efficiency = (self.thermal_efficiency * carnot_efficiency).clamp(0.0, 1.0);
+        let electrical_energy = heat_transferred * efficiency;
+        Some(Transfer {
+            heat_transferred,
+            carnot_efficiency,
+            efficiency,
+            electrical_energy: electrical_energy * self.power_factor,
+            hot_outlet_temperature: equilibrium_temperature,
+            cold_outlet_temperature: cold.temperature + (heat_transferred - electrical_energy) / cold_capacity,
+        })
+    }
+}
+
+// Reads the TegGenerator component from the TEG entity prototypes, if any entity has one.
+pub fn parse_generator(text: &str) -> Result<Option<Generator>, serde_yaml::Error> {
+    let docs: Vec<Value> = serde_yaml::from_str(text)?;
+    let component = docs
+        .iter()
+        .filter_map(|doc| doc.get("components")?.as_sequence())
+        .flatten()
+        .find(|component| component.get("type").and_then(Value::as_str) == Some("TegGenerator"));
+    let Some(component) = component else {
+        return Ok(None);
+    };
+    let defaults = Generator::default();
+    let number = |key: &str, default: f64| component.get(key).and_then(Value::as_f64).unwrap_or(default);
+    Ok(Some(Generator {
+        thermal_efficiency: number("thermalEfficiency", defaults.thermal_efficiency),
+        power_factor: number("powerFactor", defaults.power_factor),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch_generator;
+    use crate::gas::parse_gases;
+    use crate::DataSource;
+
+    fn physics() -> Physics {
+        Physics {
+            r: 8.314462618,
+            heat_scale: 1.0,
+            minimum_heat_capacity: 0.0003,
+            minimum_temperature_delta: 0.01,
+            room_temperature: 293.15,
+            cell_volume: 2500.0,
+        }
+    }
+
+    #[test]
+    fn transfer_splits_the_heat_between_power_and_the_cold_side() {
+        let gases = parse_gases("- type: gas\n  id: 0\n  name: Plasma\n  specificHeat: 200\n").unwrap();
+        let hot = Mixture { moles: vec![1.0], temperature: 1000.0, volume: 200.0 };
+        let cold = Mixture { moles: vec![1.0], temperature: 250.0, volume: 200.0 };
+        let transfer = Generator::default().transfer(&hot, &cold, &gases, &physics()).unwrap();
+        // Equal heat capacities settle halfway, giving up 200 J/K * 375 K.
+        assert_eq!(transfer.hot_outlet_temperature, 625.0);
+        assert_eq!(transfer.heat_transferred, 75000.0);
+        assert_eq!(transfer.carnot_efficiency, 0.75);
+        assert!((transfer.efficiency - 0.65 * 0.75).abs() < 1e-12);
+        assert!((transfer.electrical_energy - 75000.0 * 0.65 * 0.75).abs() < 1e-6);
+        assert!((transfer.cold_outlet_temperature - (250.0 + 75000.0 * (1.0 - 0.65 * 0.75) / 200.0)).abs() < 1e-9);
+
+        let half_power = Generator { power_factor: 0.5, ..Generator::default() };
+        assert!((half_power.transfer(&hot, &cold, &gases, &physics()).unwrap().electrical_energy - transfer.electrical_energy / 2.0).abs() < 1e-6);
+        assert_eq!(Generator::default().transfer(&cold, &hot, &gases, &physics()), None);
+        assert_eq!(Generator::default().transfer(&hot, &Mixture::new(1, 250.0, 200.0), &gases, &physics()), None);
+    }
+
+    #[test]
+    fn parses_overrides_and_falls_back_to_the_component_defaults() {
+        let overridden = "- type: entity\n  id: TegCenter\n  components:\n  - type: Sprite\n  - type: TegGenerator\n    thermalEfficiency: 0.5\n    powerFactor: 2\n";
+        assert_eq!(parse_generator(overridden).unwrap(), Some(Generator { thermal_efficiency: 0.5, power_factor: 2.0 }));
+        let plain = "- type: entity\n  id: TegCenter\n  components:\n  - type: TegGenerator\n";
+        assert_eq!(parse_generator(plain).unwrap(), Some(Generator { thermal_efficiency: 0.65, power_factor: 1.0 }));
+        let other = "- type: entity\n  id: TegCirculator\n  components:\n  - type: Sprite\n";
+        assert_eq!(parse_generator(other).unwrap(), None);
+    }
+
+    #[test]
+    fn missing_prototype_file_means_no_generator() {
+        let root = std::env::temp_dir().join("atmos-teg-missing-prototype");
+        let (generator, _) = fetch_generator(&DataSource::Local(root)).unwrap();
+        assert_eq!(generator, None);
+    }
+}
@@ -0,0 +1,29 @@
+use crate::error::{Error, Result};
+use serde_yaml::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+pub fn read_variables(path: &Path) -> Result<HashMap<String, String>> {
+    let text = fs::read_to_string(path).map_err(|source| Error::Io { path: path.to_path_buf(), source })?;
+    let file = path.display().to_string();
+    let values: BTreeMap<String, Value> = serde_yaml::from_str(&text).map_err(|source| Error::Yaml { file: file.clone(), source })?;
+    values
+        .into_iter()
+        .map(|(name, value)| match value {
+            Value::String(value) => Ok((name, value)),
+            Value::Number(value) => Ok((name, value.to_string())),
+            Value::Bool(value) => Ok((name, value.to_string())),
+            _ => Err(Error::Yaml {
+                file: file.clone(),
+                source: serde::de::Error::custom(format!("variable {} is not a number or an expression", name)),
+            }),
+        })
+        .collect()
+}
+
+pub fn write_variables(path: &Path, variables: &HashMap<String, String>) -> Result<()> {
+    let sorted: BTreeMap<&String, &String> = variables.iter().collect();
+    let text = serde_yaml::to_string(&sorted).unwrap_or_default();
+    fs::write(path, text).map_err(|source| Error::Io { path: path.to_path_buf(), source })
+}